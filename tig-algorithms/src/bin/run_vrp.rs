@@ -0,0 +1,113 @@
+// Runs one of the vehicle routing algorithms on a generated instance, e.g.
+// cargo run --bin run_vrp -- --algorithm clarke_wright --num-nodes 40 --better-than-baseline 250 --seed 0
+// Exits with 0 only if the algorithm returns a solution that passes verification
+use anyhow::{anyhow, Result};
+use std::process::ExitCode;
+use tig_algorithms::vehicle_routing::{all_solvers, routes_total_distance, FlatMatrix};
+use tig_challenges::{vehicle_routing::*, ChallengeTrait};
+
+struct Args {
+    algorithm: String,
+    num_nodes: usize,
+    better_than_baseline: u32,
+    seed: u32,
+}
+
+fn usage() -> String {
+    let names: Vec<String> = all_solvers()
+        .iter()
+        .map(|solver| solver.name().to_string())
+        .collect();
+    format!(
+        "Usage: run_vrp --algorithm <{}> --num-nodes <N> --better-than-baseline <N> [--seed <N>]",
+        names.join("|")
+    )
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args> {
+    let mut algorithm = None;
+    let mut num_nodes = None;
+    let mut better_than_baseline = None;
+    let mut seed = 0;
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| anyhow!("Missing value for {}", flag))?;
+        match flag.as_str() {
+            "--algorithm" => algorithm = Some(value),
+            "--num-nodes" => num_nodes = Some(value.parse()?),
+            "--better-than-baseline" => better_than_baseline = Some(value.parse()?),
+            "--seed" => seed = value.parse()?,
+            _ => return Err(anyhow!("Unknown argument {}", flag)),
+        }
+    }
+    Ok(Args {
+        algorithm: algorithm.ok_or_else(|| anyhow!("--algorithm is required"))?,
+        num_nodes: num_nodes.ok_or_else(|| anyhow!("--num-nodes is required"))?,
+        better_than_baseline: better_than_baseline
+            .ok_or_else(|| anyhow!("--better-than-baseline is required"))?,
+        seed,
+    })
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n{}", e, usage());
+            return ExitCode::from(2);
+        }
+    };
+    let Some(solver) = all_solvers()
+        .into_iter()
+        .find(|solver| solver.name() == args.algorithm)
+    else {
+        eprintln!("Unknown algorithm {}\n{}", args.algorithm, usage());
+        return ExitCode::from(2);
+    };
+
+    let difficulty = Difficulty {
+        num_nodes: args.num_nodes,
+        better_than_baseline: args.better_than_baseline,
+        demand_distribution: DemandDistribution::Uniform,
+    };
+    let challenge = match Challenge::generate_instance(args.seed, &difficulty) {
+        Ok(challenge) => challenge,
+        Err(e) => {
+            eprintln!("Failed to generate instance: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match solver.solve(&challenge) {
+        Ok(Some(solution)) => {
+            for route in solution.routes.iter() {
+                println!("{:?}", route);
+            }
+            let d = FlatMatrix::from_rows(&challenge.distance_matrix);
+            println!(
+                "Total distance: {} (max {})",
+                routes_total_distance(&solution.routes, &d),
+                challenge.max_total_distance
+            );
+            match challenge.verify_solution(&solution) {
+                Ok(_) => {
+                    println!("Valid solution");
+                    ExitCode::SUCCESS
+                }
+                Err(e) => {
+                    println!("Invalid solution: {}", e);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Ok(None) => {
+            println!("No solution");
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            println!("Algorithm error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
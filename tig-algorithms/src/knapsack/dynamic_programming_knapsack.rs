@@ -0,0 +1,87 @@
+/*!
+Copyright 2024 Crypti (PTY) LTD
+
+Licensed under the TIG Inbound Game License v1.0 or (at your option) any later
+version (the "License"); you may not use this file except in compliance with the
+License. You may obtain a copy of the License at
+
+https://github.com/tig-foundation/tig-monorepo/tree/main/docs/licenses
+
+Unless required by applicable law or agreed to in writing, software distributed
+under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR
+CONDITIONS OF ANY KIND, either express or implied. See the License for the specific
+language governing permissions and limitations under the License.
+*/
+
+use tig_challenges::knapsack::*;
+
+pub fn solve_challenge(challenge: &Challenge) -> anyhow::Result<Option<Solution>> {
+    let (items, total_value) = best_items(challenge);
+    if total_value < challenge.min_value {
+        return Ok(None);
+    }
+    Ok(Some(Solution { items }))
+}
+
+// Exact 0/1 knapsack over the weight capacity. Returns the selected items, in ascending order,
+// together with their total value
+pub fn best_items(challenge: &Challenge) -> (Vec<usize>, u32) {
+    let candidates: Vec<usize> = (0..challenge.difficulty.num_items).collect();
+    let mut items = Vec::new();
+    select_items(
+        challenge,
+        &candidates,
+        challenge.max_weight as usize,
+        &mut items,
+    );
+    let total_value = items.iter().map(|&item| challenge.values[item]).sum();
+    (items, total_value)
+}
+
+// Highest value within each weight up to `capacity` using only `candidates`. A single row is
+// rolled over the items, so memory stays linear in the capacity
+fn best_values(challenge: &Challenge, candidates: &[usize], capacity: usize) -> Vec<u32> {
+    let mut best = vec![0u32; capacity + 1];
+    for &i in candidates {
+        let weight = challenge.weights[i] as usize;
+        let value = challenge.values[i];
+        // Iterating downwards lets each item be taken at most once
+        for w in (weight..=capacity).rev() {
+            best[w] = best[w].max(best[w - weight] + value);
+        }
+    }
+    best
+}
+
+// Appends an optimal selection of `candidates` within `capacity` to `items`. Rather than keeping
+// a table of choices per item to trace back, the candidates are halved and the capacity split
+// where the best values of both halves add up to the optimum, then each half is solved on its
+// own share. This costs a log factor in time but keeps memory linear in the capacity
+fn select_items(
+    challenge: &Challenge,
+    candidates: &[usize],
+    capacity: usize,
+    items: &mut Vec<usize>,
+) {
+    match candidates {
+        [] => {}
+        &[i] => {
+            if challenge.weights[i] as usize <= capacity && challenge.values[i] > 0 {
+                items.push(i);
+            }
+        }
+        _ => {
+            let (left, right) = candidates.split_at(candidates.len() / 2);
+            // The rows are dropped before recursing, so at most one pair is alive at a time
+            let left_split = {
+                let left_best = best_values(challenge, left, capacity);
+                let right_best = best_values(challenge, right, capacity);
+                (0..=capacity)
+                    .max_by_key(|&w| left_best[w] + right_best[capacity - w])
+                    .unwrap()
+            };
+            select_items(challenge, left, left_split, items);
+            select_items(challenge, right, capacity - left_split, items);
+        }
+    }
+}
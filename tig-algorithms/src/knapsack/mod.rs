@@ -1995,3 +1995,86 @@ pub mod knapheudp; // c003_a019
 // c003_a998
 
 // c003_a999
+
+pub mod dynamic_programming_knapsack;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tig_challenges::{knapsack::*, *};
+
+    #[test]
+    fn test_dynamic_programming_knapsack() {
+        let difficulty = Difficulty {
+            num_items: 50,
+            better_than_baseline: 10,
+        };
+        let seed = 0; // change this to generate different instances
+        let challenge = Challenge::generate_instance(seed, &difficulty).unwrap();
+        match dynamic_programming_knapsack::solve_challenge(&challenge) {
+            Ok(Some(solution)) => match challenge.verify_solution(&solution) {
+                Ok(_) => println!("Valid solution"),
+                Err(e) => println!("Invalid solution: {}", e),
+            },
+            Ok(None) => println!("No solution"),
+            Err(e) => println!("Algorithm error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_dynamic_programming_knapsack_beats_baseline() {
+        // The greedy baseline is feasible, so the optimum always reaches it
+        let difficulty = Difficulty {
+            num_items: 50,
+            better_than_baseline: 0,
+        };
+        for seed in 0..5 {
+            let challenge = Challenge::generate_instance(seed, &difficulty).unwrap();
+            let solution = dynamic_programming_knapsack::solve_challenge(&challenge)
+                .unwrap()
+                .unwrap();
+            challenge.verify_solution(&solution).unwrap();
+
+            let (items, total_value) = dynamic_programming_knapsack::best_items(&challenge);
+            assert_eq!(items, solution.items);
+            assert_eq!(
+                total_value,
+                items
+                    .iter()
+                    .map(|&item| challenge.values[item])
+                    .sum::<u32>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_dynamic_programming_knapsack_matches_brute_force() {
+        let difficulty = Difficulty {
+            num_items: 12,
+            better_than_baseline: 0,
+        };
+        for seed in 0..5 {
+            let challenge = Challenge::generate_instance(seed, &difficulty).unwrap();
+            let optimum = (0..1u32 << difficulty.num_items)
+                .filter_map(|subset| {
+                    let chosen = (0..difficulty.num_items).filter(|&item| subset >> item & 1 == 1);
+                    let weight: u32 = chosen.clone().map(|item| challenge.weights[item]).sum();
+                    let value: u32 = chosen.map(|item| challenge.values[item]).sum();
+                    (weight <= challenge.max_weight).then_some(value)
+                })
+                .max()
+                .unwrap();
+
+            let (items, total_value) = dynamic_programming_knapsack::best_items(&challenge);
+            assert_eq!(total_value, optimum);
+            assert!(items.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(
+                items
+                    .iter()
+                    .map(|&item| challenge.weights[item])
+                    .sum::<u32>()
+                    <= challenge.max_weight
+            );
+        }
+    }
+}
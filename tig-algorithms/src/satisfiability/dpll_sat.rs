@@ -0,0 +1,166 @@
+/*!
+Copyright 2024 Crypti (PTY) LTD
+
+Licensed under the TIG Inbound Game License v1.0 or (at your option) any later
+version (the "License"); you may not use this file except in compliance with the
+License. You may obtain a copy of the License at
+
+https://github.com/tig-foundation/tig-monorepo/tree/main/docs/licenses
+
+Unless required by applicable law or agreed to in writing, software distributed
+under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR
+CONDITIONS OF ANY KIND, either express or implied. See the License for the specific
+language governing permissions and limitations under the License.
+*/
+
+use tig_challenges::satisfiability::*;
+
+// Gives up on instances that need more branching than this rather than searching indefinitely
+const MAX_DECISIONS: usize = 10_000;
+
+pub fn solve_challenge(challenge: &Challenge) -> anyhow::Result<Option<Solution>> {
+    Ok(dpll(
+        challenge.difficulty.num_variables,
+        &challenge.clauses,
+        MAX_DECISIONS,
+    )
+    .map(|variables| Solution { variables }))
+}
+
+// Iterative DPLL with unit propagation and pure literal elimination. Returns a satisfying
+// assignment, or None if the clauses are unsatisfiable or more than `max_decisions` branches
+// were needed
+pub fn dpll(num_variables: usize, clauses: &[Vec<i32>], max_decisions: usize) -> Option<Vec<bool>> {
+    let mut solver = Dpll {
+        values: vec![None; num_variables],
+        trail: Vec::new(),
+    };
+    // (trail length before the decision, variable, whether the other value has been tried)
+    let mut decisions: Vec<(usize, usize, bool)> = Vec::new();
+    let mut num_decisions = 0;
+
+    loop {
+        if !solver.propagate(clauses) {
+            // Undo decisions until one can still be flipped
+            loop {
+                let (trail_len, var, flipped) = decisions.pop()?;
+                let value = solver.values[var].unwrap();
+                solver.undo(trail_len);
+                if !flipped {
+                    decisions.push((trail_len, var, true));
+                    solver.assign(var, !value);
+                    break;
+                }
+            }
+            continue;
+        }
+
+        // Branch on an unassigned variable of the first clause not yet satisfied
+        let Some(var) = clauses
+            .iter()
+            .find(|clause| !solver.is_satisfied(clause))
+            .and_then(|clause| {
+                clause
+                    .iter()
+                    .map(|&literal| literal.unsigned_abs() as usize - 1)
+                    .find(|&var| solver.values[var].is_none())
+            })
+        else {
+            // Variables left unassigned appear in no unsatisfied clause, so any value works
+            return Some(
+                solver
+                    .values
+                    .iter()
+                    .map(|value| value.unwrap_or(false))
+                    .collect(),
+            );
+        };
+
+        num_decisions += 1;
+        if num_decisions > max_decisions {
+            return None;
+        }
+        decisions.push((solver.trail.len(), var, false));
+        solver.assign(var, true);
+    }
+}
+
+struct Dpll {
+    values: Vec<Option<bool>>,
+    // Variables in the order they were assigned, so backtracking can unassign them
+    trail: Vec<usize>,
+}
+
+impl Dpll {
+    fn assign(&mut self, var: usize, value: bool) {
+        self.values[var] = Some(value);
+        self.trail.push(var);
+    }
+
+    fn undo(&mut self, trail_len: usize) {
+        for var in self.trail.drain(trail_len..) {
+            self.values[var] = None;
+        }
+    }
+
+    fn literal_value(&self, literal: i32) -> Option<bool> {
+        self.values[literal.unsigned_abs() as usize - 1].map(|value| value == (literal > 0))
+    }
+
+    fn is_satisfied(&self, clause: &[i32]) -> bool {
+        clause
+            .iter()
+            .any(|&literal| self.literal_value(literal) == Some(true))
+    }
+
+    // Assigns unit and pure literals until neither remains. Returns false on a conflict
+    fn propagate(&mut self, clauses: &[Vec<i32>]) -> bool {
+        loop {
+            let mut changed = false;
+            for clause in clauses {
+                if self.is_satisfied(clause) {
+                    continue;
+                }
+                let mut unassigned = clause
+                    .iter()
+                    .cloned()
+                    .filter(|&literal| self.literal_value(literal).is_none());
+                let Some(first) = unassigned.next() else {
+                    return false;
+                };
+                // Repeated literals still leave the clause with a single choice
+                if unassigned.all(|literal| literal == first) {
+                    self.assign(first.unsigned_abs() as usize - 1, first > 0);
+                    changed = true;
+                }
+            }
+            if changed {
+                continue;
+            }
+
+            // A variable that only appears with one sign in the unsatisfied clauses can take
+            // that sign without ruling out any solution
+            let mut seen_positive = vec![false; self.values.len()];
+            let mut seen_negative = vec![false; self.values.len()];
+            for clause in clauses.iter().filter(|clause| !self.is_satisfied(clause)) {
+                for &literal in clause {
+                    let var = literal.unsigned_abs() as usize - 1;
+                    if literal > 0 {
+                        seen_positive[var] = true;
+                    } else {
+                        seen_negative[var] = true;
+                    }
+                }
+            }
+            for var in 0..self.values.len() {
+                if self.values[var].is_none() && seen_positive[var] != seen_negative[var] {
+                    self.assign(var, seen_positive[var]);
+                    changed = true;
+                }
+            }
+            if !changed {
+                return true;
+            }
+        }
+    }
+}
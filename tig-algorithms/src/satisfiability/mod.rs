@@ -1995,3 +1995,39 @@ pub mod inbound; // c001_a018
 // c001_a998
 
 // c001_a999
+
+pub mod dpll_sat;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tig_challenges::{satisfiability::*, *};
+
+    #[test]
+    fn test_dpll_sat() {
+        let difficulty = Difficulty {
+            num_variables: 50,
+            clauses_to_variables_percent: 300,
+        };
+        let seed = 0; // change this to generate different instances
+        let challenge = Challenge::generate_instance(seed, &difficulty).unwrap();
+        let solution = dpll_sat::solve_challenge(&challenge)
+            .unwrap()
+            .expect("instances well below the phase transition should be satisfiable");
+        challenge.verify_solution(&solution).unwrap();
+    }
+
+    #[test]
+    fn test_dpll_sat_unsatisfiable() {
+        // Every combination of values for three variables falsifies one of the clauses
+        let clauses: Vec<Vec<i32>> = (0..8)
+            .map(|signs: i32| {
+                (0..3)
+                    .map(|var| if signs >> var & 1 == 1 { var + 1 } else { -(var + 1) })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(dpll_sat::dpll(3, &clauses, 1000), None);
+        assert!(dpll_sat::dpll(3, &clauses[1..], 1000).is_some());
+    }
+}
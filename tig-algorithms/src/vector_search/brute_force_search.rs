@@ -0,0 +1,51 @@
+/*!
+Copyright 2024 Crypti (PTY) LTD
+
+Licensed under the TIG Inbound Game License v1.0 or (at your option) any later
+version (the "License"); you may not use this file except in compliance with the
+License. You may obtain a copy of the License at
+
+https://github.com/tig-foundation/tig-monorepo/tree/main/docs/licenses
+
+Unless required by applicable law or agreed to in writing, software distributed
+under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR
+CONDITIONS OF ANY KIND, either express or implied. See the License for the specific
+language governing permissions and limitations under the License.
+*/
+
+use tig_challenges::vector_search::*;
+
+pub fn solve_challenge(challenge: &Challenge) -> anyhow::Result<Option<Solution>> {
+    let mut total_distance = 0.0;
+    let mut indexes = Vec::with_capacity(challenge.query_vectors.len());
+    for query in challenge.query_vectors.iter() {
+        let mut best_index = 0;
+        let mut best_squared = f32::MAX;
+        for (index, vector) in challenge.vector_database.iter().enumerate() {
+            if let Some(squared) = squared_distance_below(query, vector, best_squared) {
+                best_index = index;
+                best_squared = squared;
+            }
+        }
+        indexes.push(best_index);
+        total_distance += best_squared.sqrt();
+    }
+
+    if total_distance / indexes.len() as f32 > challenge.max_distance {
+        return Ok(None);
+    }
+    Ok(Some(Solution { indexes }))
+}
+
+// Squared Euclidean distance between `a` and `b`, or None as soon as it reaches `limit`
+#[inline]
+fn squared_distance_below(a: &[f32], b: &[f32], limit: f32) -> Option<f32> {
+    let mut sum = 0.0;
+    for (&x1, &x2) in a.iter().zip(b) {
+        sum += (x1 - x2) * (x1 - x2);
+        if sum >= limit {
+            return None;
+        }
+    }
+    Some(sum)
+}
@@ -1995,3 +1995,59 @@
 // c004_a998
 
 // c004_a999
+
+pub mod brute_force_search;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tig_challenges::{vector_search::*, *};
+
+    #[test]
+    fn test_brute_force_search() {
+        let difficulty = Difficulty {
+            num_queries: 10,
+            better_than_baseline: 350,
+        };
+        let seed = 0; // change this to generate different instances
+        let challenge = Challenge::generate_instance(seed, &difficulty).unwrap();
+        let solution = brute_force_search::solve_challenge(&challenge)
+            .unwrap()
+            .expect("nearest neighbours should meet the max distance");
+        challenge.verify_solution(&solution).unwrap();
+    }
+
+    #[test]
+    fn test_brute_force_search_finds_nearest_neighbours() {
+        // Stopping each query at the first vector within the max distance used to return None on
+        // this instance, as the average needs the slack of the queries that are closer
+        let difficulty = Difficulty {
+            num_queries: 10,
+            better_than_baseline: 550,
+        };
+        let challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        let nearest: Vec<usize> = challenge
+            .query_vectors
+            .iter()
+            .map(|query| {
+                (0..challenge.vector_database.len())
+                    .min_by(|&a, &b| {
+                        euclidean_distance(query, &challenge.vector_database[a])
+                            .total_cmp(&euclidean_distance(query, &challenge.vector_database[b]))
+                    })
+                    .unwrap()
+            })
+            .collect();
+
+        let solution = brute_force_search::solve_challenge(&challenge).unwrap();
+        let nearest_verifies = challenge
+            .verify_solution(&Solution {
+                indexes: nearest.clone(),
+            })
+            .is_ok();
+        assert_eq!(
+            solution.map(|solution| solution.indexes),
+            nearest_verifies.then_some(nearest)
+        );
+    }
+}
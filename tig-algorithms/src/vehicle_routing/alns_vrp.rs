@@ -0,0 +1,358 @@
+/*!
+Copyright 2024 Crypti (PTY) LTD
+
+Licensed under the TIG Inbound Game License v1.0 or (at your option) any later
+version (the "License"); you may not use this file except in compliance with the
+License. You may obtain a copy of the License at
+
+https://github.com/tig-foundation/tig-monorepo/tree/main/docs/licenses
+
+Unless required by applicable law or agreed to in writing, software distributed
+under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR
+CONDITIONS OF ANY KIND, either express or implied. See the License for the specific
+language governing permissions and limitations under the License.
+*/
+
+use super::{clarke_wright_merge_vrp, routes_total_distance, FlatMatrix};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tig_challenges::vehicle_routing::*;
+
+const MAX_ITERATIONS: usize = 2000;
+// Operator weights are updated from their scores at the end of every segment
+const SEGMENT_LENGTH: usize = 100;
+const REACTION_FACTOR: f64 = 0.1;
+// Scores for producing a new global best, improving on the current solution, or being accepted
+const SCORE_BEST: f64 = 33.0;
+const SCORE_BETTER: f64 = 9.0;
+const SCORE_ACCEPTED: f64 = 13.0;
+// Initial temperature accepts a solution 5% worse than the start with probability 0.5
+const START_WORSENING: f64 = 0.05;
+const COOLING_RATE: f64 = 0.9975;
+
+const DESTROY_OPERATORS: [Destroy; 3] = [Destroy::Random, Destroy::Worst, Destroy::Related];
+const REPAIR_OPERATORS: [Repair; 2] = [Repair::Greedy, Repair::Regret];
+
+#[derive(Clone, Copy)]
+enum Destroy {
+    Random,
+    // Customers whose removal saves the most distance
+    Worst,
+    // Customers close to a randomly chosen customer (Shaw removal)
+    Related,
+}
+
+#[derive(Clone, Copy)]
+enum Repair {
+    Greedy,
+    Regret,
+}
+
+pub fn solve_challenge(challenge: &Challenge) -> anyhow::Result<Option<Solution>> {
+    if !super::demands_fit_capacity(challenge) {
+        return Ok(None);
+    }
+
+    let (routes, total_distance) = adaptive_large_neighborhood_search(challenge);
+    Ok(super::finish_solution(challenge, &routes, total_distance))
+}
+
+pub fn solve_best_effort(challenge: &Challenge) -> anyhow::Result<Solution> {
+    super::check_demands_fit_capacity(challenge)?;
+    let (routes, _) = adaptive_large_neighborhood_search(challenge);
+    let routes = super::canonicalize_routes(&routes, &challenge.depots);
+    Ok(Solution { routes })
+}
+
+// Ruin-and-recreate search starting from the Clarke-Wright routes. Returns the best routes
+// seen together with their total distance
+pub fn adaptive_large_neighborhood_search(challenge: &Challenge) -> (Vec<Vec<usize>>, i64) {
+    let d = &FlatMatrix::from_rows(&challenge.distance_matrix);
+    let demands = &challenge.demands;
+    let c = challenge.max_capacity;
+    let max_total_distance = challenge.max_total_distance as i64;
+    let depots = &challenge.depots;
+    let num_nodes = challenge.difficulty.num_nodes;
+    let num_customers = num_nodes - depots.len();
+    let mut rng = StdRng::seed_from_u64(challenge.seed as u64);
+
+    let (mut current, mut current_distance) = clarke_wright_merge_vrp::build_routes(challenge, d);
+    let mut best = current.clone();
+    let mut best_distance = current_distance;
+    if num_customers == 0 {
+        return (best, best_distance);
+    }
+
+    let max_remove = (num_customers * 3 / 10).max(1);
+    let min_remove = (num_customers / 10).clamp(1, max_remove);
+    let mut temperature =
+        (START_WORSENING * current_distance as f64 / std::f64::consts::LN_2).max(1.0);
+
+    let mut destroy_weights = [1.0; DESTROY_OPERATORS.len()];
+    let mut destroy_scores = [0.0; DESTROY_OPERATORS.len()];
+    let mut destroy_uses = [0; DESTROY_OPERATORS.len()];
+    let mut repair_weights = [1.0; REPAIR_OPERATORS.len()];
+    let mut repair_scores = [0.0; REPAIR_OPERATORS.len()];
+    let mut repair_uses = [0; REPAIR_OPERATORS.len()];
+
+    for iteration in 0..MAX_ITERATIONS {
+        if best_distance <= max_total_distance {
+            break;
+        }
+
+        let destroy_idx = roulette(&destroy_weights, &mut rng);
+        let repair_idx = roulette(&repair_weights, &mut rng);
+        let num_remove = rng.gen_range(min_remove..=max_remove);
+
+        let mut candidate = current.clone();
+        let removed = match DESTROY_OPERATORS[destroy_idx] {
+            Destroy::Random => random_removal(&candidate, num_remove, &mut rng),
+            Destroy::Worst => worst_removal(&candidate, num_remove, d, &mut rng),
+            Destroy::Related => related_removal(&candidate, num_remove, d, &mut rng),
+        };
+        remove_customers(&mut candidate, &removed, num_nodes);
+        match REPAIR_OPERATORS[repair_idx] {
+            Repair::Greedy => greedy_insertion(&mut candidate, removed, depots, d, demands, c),
+            Repair::Regret => regret_insertion(&mut candidate, removed, depots, d, demands, c),
+        }
+        let candidate_distance = routes_total_distance(&candidate, d);
+
+        let mut score = 0.0;
+        if candidate_distance < best_distance {
+            best = candidate.clone();
+            best_distance = candidate_distance;
+            score = SCORE_BEST;
+        } else if candidate_distance < current_distance {
+            score = SCORE_BETTER;
+        } else if rng.gen::<f64>()
+            < (-((candidate_distance - current_distance) as f64) / temperature).exp()
+        {
+            score = SCORE_ACCEPTED;
+        }
+        if score > 0.0 {
+            current = candidate;
+            current_distance = candidate_distance;
+        }
+        temperature *= COOLING_RATE;
+
+        destroy_scores[destroy_idx] += score;
+        destroy_uses[destroy_idx] += 1;
+        repair_scores[repair_idx] += score;
+        repair_uses[repair_idx] += 1;
+        if (iteration + 1) % SEGMENT_LENGTH == 0 {
+            update_weights(&mut destroy_weights, &mut destroy_scores, &mut destroy_uses);
+            update_weights(&mut repair_weights, &mut repair_scores, &mut repair_uses);
+        }
+    }
+
+    (best, best_distance)
+}
+
+fn roulette(weights: &[f64], rng: &mut StdRng) -> usize {
+    let mut r = rng.gen::<f64>() * weights.iter().sum::<f64>();
+    for (i, &weight) in weights.iter().enumerate() {
+        if r < weight {
+            return i;
+        }
+        r -= weight;
+    }
+    weights.len() - 1
+}
+
+fn update_weights(weights: &mut [f64], scores: &mut [f64], uses: &mut [usize]) {
+    for i in 0..weights.len() {
+        if uses[i] > 0 {
+            weights[i] =
+                (1.0 - REACTION_FACTOR) * weights[i] + REACTION_FACTOR * scores[i] / uses[i] as f64;
+        }
+        scores[i] = 0.0;
+        uses[i] = 0;
+    }
+}
+
+fn customers(routes: &[Vec<usize>]) -> Vec<usize> {
+    routes
+        .iter()
+        .flat_map(|route| route[1..route.len() - 1].iter().cloned())
+        .collect()
+}
+
+// Removes the given customers, dropping routes left without any
+fn remove_customers(routes: &mut Vec<Vec<usize>>, removed: &[usize], num_nodes: usize) {
+    let mut is_removed = vec![false; num_nodes];
+    for &node in removed {
+        is_removed[node] = true;
+    }
+    for route in routes.iter_mut() {
+        route.retain(|&node| !is_removed[node]);
+    }
+    routes.retain(|route| route.len() > 2);
+}
+
+fn random_removal(routes: &[Vec<usize>], num_remove: usize, rng: &mut StdRng) -> Vec<usize> {
+    let mut candidates = customers(routes);
+    (0..num_remove.min(candidates.len()))
+        .map(|_| candidates.swap_remove(rng.gen_range(0..candidates.len())))
+        .collect()
+}
+
+fn worst_removal(
+    routes: &[Vec<usize>],
+    num_remove: usize,
+    d: &FlatMatrix,
+    rng: &mut StdRng,
+) -> Vec<usize> {
+    // Distance saved by removing each customer from its current position
+    let mut savings: Vec<(i32, usize)> = routes
+        .iter()
+        .flat_map(|route| {
+            route.windows(3).map(|w| {
+                let (prev, node, next) = (w[0], w[1], w[2]);
+                (
+                    d.get(prev, node) + d.get(node, next) - d.get(prev, next),
+                    node,
+                )
+            })
+        })
+        .collect();
+    savings.sort_unstable_by(|a, b| b.cmp(a));
+
+    // Biased towards the largest savings, with some randomness to diversify
+    (0..num_remove.min(savings.len()))
+        .map(|_| {
+            let idx = (rng.gen::<f64>().powi(3) * savings.len() as f64) as usize;
+            savings.remove(idx).1
+        })
+        .collect()
+}
+
+fn related_removal(
+    routes: &[Vec<usize>],
+    num_remove: usize,
+    d: &FlatMatrix,
+    rng: &mut StdRng,
+) -> Vec<usize> {
+    let mut candidates = customers(routes);
+    let seed = candidates[rng.gen_range(0..candidates.len())];
+    candidates.sort_unstable_by_key(|&node| (d.get(seed, node), node));
+    candidates.truncate(num_remove);
+    candidates
+}
+
+// Cheapest feasible insertion of `node` into each route, plus opening a new route, as
+// (cost, route index, position). A route index of `routes.len()` stands for a new route from
+// the depot nearest `node`, which is given in place of the position
+fn insertion_options(
+    routes: &[Vec<usize>],
+    loads: &[i32],
+    node: usize,
+    depots: &[usize],
+    d: &FlatMatrix,
+    demands: &[i32],
+    c: i32,
+) -> Vec<(i64, usize, usize)> {
+    let mut options: Vec<(i64, usize, usize)> = routes
+        .iter()
+        .enumerate()
+        .filter(|&(r, _)| loads[r] + demands[node] <= c)
+        .map(|(r, route)| {
+            (1..route.len())
+                .map(|q| {
+                    let (x, y) = (route[q - 1], route[q]);
+                    let cost = d.get(x, node) + d.get(node, y) - d.get(x, y);
+                    (cost as i64, r, q)
+                })
+                .min()
+                .unwrap()
+        })
+        .collect();
+    options.push(
+        depots
+            .iter()
+            .map(|&depot| {
+                let cost = d.get(depot, node) as i64 + d.get(node, depot) as i64;
+                (cost, routes.len(), depot)
+            })
+            .min()
+            .unwrap(),
+    );
+    options.sort_unstable();
+    options
+}
+
+fn insert(
+    routes: &mut Vec<Vec<usize>>,
+    loads: &mut Vec<i32>,
+    node: usize,
+    route: usize,
+    position: usize,
+    demands: &[i32],
+) {
+    if route == routes.len() {
+        let depot = position;
+        routes.push(vec![depot, node, depot]);
+        loads.push(demands[node]);
+    } else {
+        routes[route].insert(position, node);
+        loads[route] += demands[node];
+    }
+}
+
+fn route_loads(routes: &[Vec<usize>], demands: &[i32]) -> Vec<i32> {
+    routes
+        .iter()
+        .map(|route| route.iter().map(|&node| demands[node]).sum())
+        .collect()
+}
+
+// Repeatedly performs the cheapest insertion over all pending customers
+fn greedy_insertion(
+    routes: &mut Vec<Vec<usize>>,
+    mut pending: Vec<usize>,
+    depots: &[usize],
+    d: &FlatMatrix,
+    demands: &[i32],
+    c: i32,
+) {
+    let mut loads = route_loads(routes, demands);
+    while !pending.is_empty() {
+        let (k, (_, route, position)) = pending
+            .iter()
+            .map(|&node| insertion_options(routes, &loads, node, depots, d, demands, c)[0])
+            .enumerate()
+            .min_by_key(|&(k, option)| (option, k))
+            .unwrap();
+        let node = pending.swap_remove(k);
+        insert(routes, &mut loads, node, route, position, demands);
+    }
+}
+
+// Repeatedly inserts the pending customer with the largest gap between its best and second
+// best route, as delaying it is the most costly
+fn regret_insertion(
+    routes: &mut Vec<Vec<usize>>,
+    mut pending: Vec<usize>,
+    depots: &[usize],
+    d: &FlatMatrix,
+    demands: &[i32],
+    c: i32,
+) {
+    let mut loads = route_loads(routes, demands);
+    while !pending.is_empty() {
+        let (k, (_, route, position)) = pending
+            .iter()
+            .map(|&node| {
+                let options = insertion_options(routes, &loads, node, depots, d, demands, c);
+                let regret = match options.get(1) {
+                    Some(second) => second.0 - options[0].0,
+                    None => i64::MAX,
+                };
+                (regret, options[0])
+            })
+            .enumerate()
+            .max_by_key(|&(k, (regret, option))| (regret, std::cmp::Reverse((option.0, k))))
+            .map(|(k, (_, option))| (k, option))
+            .unwrap();
+        let node = pending.swap_remove(k);
+        insert(routes, &mut loads, node, route, position, demands);
+    }
+}
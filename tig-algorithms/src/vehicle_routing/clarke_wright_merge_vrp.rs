@@ -13,47 +13,178 @@ CONDITIONS OF ANY KIND, either express or implied. See the License for the speci
 language governing permissions and limitations under the License.
 */  
 
+use super::{improve_route, routes_total_distance, FlatMatrix, RouteImprovement};
+use std::slice;
+use std::time::{Duration, Instant};
 use tig_challenges::vehicle_routing::*;
 
 pub fn solve_challenge(challenge: &Challenge) -> anyhow::Result<Option<Solution>> {
-    let d = &challenge.distance_matrix;
+    solve(challenge, None, 1.0, RouteImprovement::TwoOpt)
+}
+
+// Ranks merges by the generalised saving d[i][0] + d[0][j] - lambda * d[i][j]. Values above 1
+// favour joining nearby customers, values below 1 favour customers far from the depot
+pub fn solve_challenge_with_lambda(
+    challenge: &Challenge,
+    lambda: f64,
+) -> anyhow::Result<Option<Solution>> {
+    solve(challenge, None, lambda, RouteImprovement::TwoOpt)
+}
+
+// Post-processes each merged route with `improvement` instead of two-opt
+pub fn solve_challenge_with_improvement(
+    challenge: &Challenge,
+    improvement: RouteImprovement,
+) -> anyhow::Result<Option<Solution>> {
+    solve(challenge, None, 1.0, improvement)
+}
+
+// Stops merging once `budget` has elapsed and returns the routes built so far. Like
+// `solve_best_effort`, these are returned even when they miss max_total_distance or max_routes,
+// so a timeout only gives None if some customer's demand cannot fit in a vehicle.
+// Wall-clock time is not available (nor deterministic) inside the WASM runtime, so
+// `solve_challenge` itself never consults the clock.
+pub fn solve_challenge_with_budget(
+    challenge: &Challenge,
+    budget: Duration,
+) -> anyhow::Result<Option<Solution>> {
+    solve(
+        challenge,
+        Some(Instant::now() + budget),
+        1.0,
+        RouteImprovement::TwoOpt,
+    )
+}
+
+fn solve(
+    challenge: &Challenge,
+    deadline: Option<Instant>,
+    lambda: f64,
+    improvement: RouteImprovement,
+) -> anyhow::Result<Option<Solution>> {
+    if !super::demands_fit_capacity(challenge) {
+        return Ok(None);
+    }
+
+    let d = &FlatMatrix::from_rows(&challenge.distance_matrix);
+    let (mut routes, _, timed_out) = merge_routes(challenge, d, deadline, lambda);
+    // Merging stopped on the deadline, so skip the post-processing and return the routes as
+    // they are
+    if timed_out {
+        let routes = super::canonicalize_routes(&routes, &challenge.depots);
+        return Ok(Some(Solution { routes }));
+    }
+    let total_distance = improve_routes_with(&mut routes, challenge, d, improvement);
+    Ok(super::finish_solution(challenge, &routes, total_distance))
+}
+
+// Returns the improved Clarke-Wright routes even when they miss max_total_distance or
+// max_routes. Fails only if some customer's demand cannot fit in a vehicle
+pub fn solve_best_effort(challenge: &Challenge) -> anyhow::Result<Solution> {
+    super::check_demands_fit_capacity(challenge)?;
+    let d = &FlatMatrix::from_rows(&challenge.distance_matrix);
+    let (mut routes, _, _) = merge_routes(challenge, d, None, 1.0);
+    improve_routes(&mut routes, challenge, d);
+    let routes = super::canonicalize_routes(&routes, &challenge.depots);
+    Ok(Solution { routes })
+}
+
+// Returns the Clarke-Wright routes and their total distance, regardless of whether they meet
+// max_total_distance. Used as a starting point by the other solvers, which pass in the
+// `FlatMatrix` of the challenge they already built
+pub fn build_routes(challenge: &Challenge, d: &FlatMatrix) -> (Vec<Vec<usize>>, i64) {
+    let (routes, total_distance, _) = merge_routes(challenge, d, None, 1.0);
+    (routes, total_distance)
+}
+
+// Also returns whether merging in any cluster was cut short by `deadline`
+fn merge_routes(
+    challenge: &Challenge,
+    d: &FlatMatrix,
+    deadline: Option<Instant>,
+    lambda: f64,
+) -> (Vec<Vec<usize>>, i64, bool) {
+    let n = challenge.difficulty.num_nodes;
+    let depots = &challenge.depots;
+
+    // Every customer is served from its nearest depot, and routes are only merged within a
+    // depot's cluster. With the single depot 0 this is one cluster of every other node
+    let mut clusters: Vec<Vec<usize>> = vec![Vec::new(); depots.len()];
+    for customer in (0..n).filter(|node| !depots.contains(node)) {
+        let nearest = (0..depots.len())
+            .min_by_key(|&k| d.get(depots[k], customer))
+            .unwrap();
+        clusters[nearest].push(customer);
+    }
+
+    // Routes across all clusters, which is what max_routes limits
+    let mut num_routes = n - depots.len();
+    let mut routes = Vec::new();
+    let mut total_distance = 0;
+    let mut timed_out = false;
+    for (&depot, customers) in depots.iter().zip(&clusters) {
+        let (cluster_routes, cluster_distance, cluster_timed_out) = merge_cluster(
+            challenge,
+            d,
+            depot,
+            customers,
+            &mut num_routes,
+            deadline,
+            lambda,
+        );
+        routes.extend(cluster_routes);
+        total_distance += cluster_distance;
+        timed_out |= cluster_timed_out;
+    }
+    (routes, total_distance, timed_out)
+}
+
+fn merge_cluster(
+    challenge: &Challenge,
+    d: &FlatMatrix,
+    depot: usize,
+    customers: &[usize],
+    num_routes: &mut usize,
+    deadline: Option<Instant>,
+    lambda: f64,
+) -> (Vec<Vec<usize>>, i64, bool) {
     let c = challenge.max_capacity;
-    let max_total_distance = challenge.max_total_distance;
     let n = challenge.difficulty.num_nodes;
+    let m = customers.len();
 
     // Clarke-Wright heuristic for node pairs based on their distances to depot
-    let mut scores: Vec<(i32, usize, usize)> = Vec::with_capacity((n * (n - 1)) / 2);
-    for i in 1..n {
-        let d_i0 = d[i][0]; // Cache this value to avoid repeated lookups
-        for j in (i + 1)..n {
-            let score = d_i0 + d[0][j] - d[i][j];
+    let mut scores: Vec<(f64, usize, usize)> = Vec::with_capacity((m * m.saturating_sub(1)) / 2);
+    for (k, &i) in customers.iter().enumerate() {
+        let d_i0 = d.get(i, depot) as f64; // Cache this value to avoid repeated lookups
+        for &j in &customers[k + 1..] {
+            let score = d_i0 + d.get(depot, j) as f64 - lambda * d.get(i, j) as f64;
             scores.push((score, i, j));
         }
     }
 
-    // Sort in descending order by score
-    scores.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+    // Sort in descending order by score, breaking ties by node pair so the merge order does
+    // not depend on the sort implementation
+    scores.sort_unstable_by(|a, b| b.0.total_cmp(&a.0).then((a.1, a.2).cmp(&(b.1, b.2))));
 
-    // Create a route for every node
-    let mut routes: Vec<Option<Vec<usize>>> = (0..n).map(|i| Some(vec![i])).collect();
-    routes[0] = None; // Depot does not need a route
-    let mut route_demands: Vec<i32> = challenge.demands.clone();
-    let mut route_distances: Vec<i32> = vec![0; n]; // Cache route distances to avoid recomputation
-
-    // A function to calculate the total distance of a route, including the return to the depot
-    fn calculate_route_distance(route: &Vec<usize>, d: &Vec<Vec<i32>>) -> i32 {
-        let mut total_distance = 0;
-        let mut last_node = 0; // Start from the depot
-        for &node in route {
-            total_distance += d[last_node][node];
-            last_node = node;
-        }
-        total_distance += d[last_node][0]; // Return to the depot
-        total_distance
+    // Create a route for every customer
+    let mut routes: Vec<Option<Vec<usize>>> = vec![None; n];
+    for &i in customers {
+        routes[i] = Some(vec![i]);
     }
+    let mut route_demands: Vec<i32> = challenge.demands.clone();
+    // Cache route distances to avoid recomputation, initially every node is a round trip from the depot
+    let mut route_distances: Vec<i64> = (0..n).map(|i| d.get(depot, i) as i64 + d.get(i, depot) as i64).collect();
+    let mut total_distance: i64 = customers.iter().map(|&i| route_distances[i]).sum();
 
     // Iterate through node pairs, starting from greatest score
+    let mut timed_out = false;
     for (s, i, j) in scores {
+        // Every route is complete and within capacity between merges, so stopping here is safe
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            timed_out = true;
+            break;
+        }
+
         if routes[i].is_none() || routes[j].is_none() {
             continue;
         }
@@ -70,30 +201,36 @@ pub fn solve_challenge(challenge: &Challenge) -> anyhow::Result<Option<Solution>
             continue;
         }
 
-        // Merge routes
-        let mut left_route = routes[i].take().unwrap();
-        let mut right_route = routes[j].take().unwrap();
-
-        // Reverse if needed to make endpoints match
+        // Merge routes, reversing if needed to make endpoints match
+        let mut new_route = left_route.clone();
+        let mut right_route = right_route.clone();
         if left_startnode == i {
-            left_route.reverse();
+            new_route.reverse();
         }
         if right_endnode == j {
             right_route.reverse();
         }
-
-        let mut new_route = left_route;
         new_route.extend(right_route);
 
         // Calculate the total distance of the new route
-        let new_route_distance = calculate_route_distance(&new_route, d);
+        let new_route_distance = route_distance_from(&new_route, depot, d);
 
-        // Ensure the new route's distance does not exceed the maximum allowed total distance
-        if new_route_distance as f64 > max_total_distance as f64 * 1.05 {
-            continue; // Skip this merge if it exceeds the max distance constraint significantly
+        // Skip merges that would increase the total distance across all routes, unless
+        // there are still more routes than allowed
+        let saving =
+            route_distances[left_startnode] + route_distances[right_startnode] - new_route_distance;
+        let over_route_limit = challenge
+            .max_routes
+            .is_some_and(|max_routes| *num_routes > max_routes);
+        if saving < 0 && !over_route_limit {
+            continue;
         }
+        total_distance -= saving;
+        *num_routes -= 1;
 
         // Update the routes, demands, and distances
+        routes[i] = None;
+        routes[j] = None;
         let (start, end) = (*new_route.first().unwrap(), *new_route.last().unwrap());
         routes[start] = Some(new_route.clone());
         routes[end] = Some(new_route);
@@ -109,9 +246,9 @@ pub fn solve_challenge(challenge: &Challenge) -> anyhow::Result<Option<Solution>
         .filter_map(|(i, opt_route)| {
             if let Some(mut route) = opt_route {
                 if route[0] == i {
-                    let mut full_route = vec![0];
+                    let mut full_route = vec![depot];
                     full_route.append(&mut route);
-                    full_route.push(0);
+                    full_route.push(depot);
                     return Some(full_route);
                 }
             }
@@ -119,5 +256,98 @@ pub fn solve_challenge(challenge: &Challenge) -> anyhow::Result<Option<Solution>
         })
         .collect();
 
-    Ok(Some(Solution { routes: final_routes }))
+    (final_routes, total_distance, timed_out)
+}
+
+// Post-processes the merged routes with two-opt on each route followed by a single relocate
+// pass. Changes are only kept if they shorten a route, so the total distance never increases.
+// Returns the new total distance
+pub fn improve_routes(routes: &mut Vec<Vec<usize>>, challenge: &Challenge, d: &FlatMatrix) -> i64 {
+    improve_routes_with(routes, challenge, d, RouteImprovement::TwoOpt)
+}
+
+// Like `improve_routes`, but improves each route with `improvement` before the relocate pass
+pub fn improve_routes_with(
+    routes: &mut Vec<Vec<usize>>,
+    challenge: &Challenge,
+    d: &FlatMatrix,
+    improvement: RouteImprovement,
+) -> i64 {
+    let demands = &challenge.demands;
+    let c = challenge.max_capacity;
+
+    for route in routes.iter_mut() {
+        let mut improved = route.clone();
+        if improve_route(&mut improved, |i, j| d.get(i, j) as i64, improvement)
+            && routes_total_distance(slice::from_ref(&improved), d)
+                < routes_total_distance(slice::from_ref(route), d)
+        {
+            *route = improved;
+        }
+    }
+
+    // Move each customer to its cheapest feasible position in any route, if that is shorter
+    let mut loads: Vec<i32> = routes
+        .iter()
+        .map(|route| route.iter().map(|&node| demands[node]).sum())
+        .collect();
+    for a in 0..routes.len() {
+        let mut p = 1;
+        while p < routes[a].len() - 1 {
+            let u = routes[a][p];
+            let (prev, next) = (routes[a][p - 1], routes[a][p + 1]);
+            let remove_u = d.get(prev, next) - d.get(prev, u) - d.get(u, next);
+
+            let mut best_move: Option<(usize, usize)> = None;
+            let mut best_delta = 0;
+            for b in 0..routes.len() {
+                if b != a && loads[b] + demands[u] > c {
+                    continue;
+                }
+                for q in 1..routes[b].len() {
+                    // Inserting next to its current position leaves the route unchanged
+                    if b == a && (q == p || q == p + 1) {
+                        continue;
+                    }
+                    let (x, y) = (routes[b][q - 1], routes[b][q]);
+                    let delta = remove_u + d.get(x, u) + d.get(u, y) - d.get(x, y);
+                    if delta < best_delta {
+                        best_move = Some((b, q));
+                        best_delta = delta;
+                    }
+                }
+            }
+
+            match best_move {
+                Some((b, q)) => {
+                    routes[a].remove(p);
+                    let q = if b == a && q > p { q - 1 } else { q };
+                    routes[b].insert(q, u);
+                    loads[a] -= demands[u];
+                    loads[b] += demands[u];
+                }
+                None => p += 1,
+            }
+        }
+    }
+    routes.retain(|route| route.len() > 2);
+
+    routes_total_distance(routes, d)
+}
+
+// Calculates the total distance of a route, including the trips from and back to the depot.
+// Accumulates in i64 as long routes over large distances can overflow i32
+pub fn calculate_route_distance(route: &[usize], d: &FlatMatrix) -> i64 {
+    route_distance_from(route, 0, d)
+}
+
+fn route_distance_from(route: &[usize], depot: usize, d: &FlatMatrix) -> i64 {
+    let mut total_distance = 0;
+    let mut last_node = depot; // Start from the depot
+    for &node in route {
+        total_distance += d.get(last_node, node) as i64;
+        last_node = node;
+    }
+    total_distance += d.get(last_node, depot) as i64; // Return to the depot
+    total_distance
 }
@@ -0,0 +1,270 @@
+/*!
+Copyright 2024 Crypti (PTY) LTD
+
+Licensed under the TIG Inbound Game License v1.0 or (at your option) any later
+version (the "License"); you may not use this file except in compliance with the
+License. You may obtain a copy of the License at
+
+https://github.com/tig-foundation/tig-monorepo/tree/main/docs/licenses
+
+Unless required by applicable law or agreed to in writing, software distributed
+under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR
+CONDITIONS OF ANY KIND, either express or implied. See the License for the specific
+language governing permissions and limitations under the License.
+*/
+
+use super::{clarke_wright_merge_vrp, FlatMatrix};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tig_challenges::vehicle_routing::*;
+
+const POPULATION_SIZE: usize = 20;
+const MAX_GENERATIONS: usize = 2000;
+const TOURNAMENT_SIZE: usize = 3;
+const MUTATION_RATE: f64 = 0.3;
+// Randomised nearest-neighbour starts pick uniformly among this many closest candidates
+const NEIGHBOR_CHOICES: usize = 3;
+
+pub fn solve_challenge(challenge: &Challenge) -> anyhow::Result<Option<Solution>> {
+    if !super::demands_fit_capacity(challenge) {
+        return Ok(None);
+    }
+
+    let (routes, total_distance) = genetic_algorithm(challenge);
+    Ok(super::finish_solution(challenge, &routes, total_distance))
+}
+
+pub fn solve_best_effort(challenge: &Challenge) -> anyhow::Result<Solution> {
+    super::check_demands_fit_capacity(challenge)?;
+    let (routes, _) = genetic_algorithm(challenge);
+    let routes = super::canonicalize_routes(&routes, &challenge.depots);
+    Ok(Solution { routes })
+}
+
+// Steady-state genetic algorithm over a population seeded with the Clarke-Wright routes and
+// randomised nearest-neighbour routes. Each generation breeds one child from two tournament
+// winners, which replaces the worst individual if it is better and its distance is not already
+// in the population. Returns the best routes seen together with their total distance
+pub fn genetic_algorithm(challenge: &Challenge) -> (Vec<Vec<usize>>, i64) {
+    let d = &FlatMatrix::from_rows(&challenge.distance_matrix);
+    let demands = &challenge.demands;
+    let c = challenge.max_capacity;
+    let max_total_distance = challenge.max_total_distance as i64;
+    let num_nodes = challenge.difficulty.num_nodes;
+    let depots = &challenge.depots;
+    let customers: Vec<usize> = (0..num_nodes)
+        .filter(|node| !depots.contains(node))
+        .collect();
+    let mut rng = StdRng::seed_from_u64(challenge.seed as u64);
+
+    // Every individual is improved with the Clarke-Wright post-processing, which never increases
+    // its distance, so the best individual is never worse than the Clarke-Wright routes
+    let (mut cw_routes, _) = clarke_wright_merge_vrp::build_routes(challenge, d);
+    let cw_distance = clarke_wright_merge_vrp::improve_routes(&mut cw_routes, challenge, d);
+    if customers.len() < 2 {
+        return (cw_routes, cw_distance);
+    }
+
+    let mut population: Vec<(Vec<Vec<usize>>, i64)> = vec![(cw_routes, cw_distance)];
+    while population.len() < POPULATION_SIZE {
+        let mut routes = randomized_nearest_neighbor(&customers, depots, d, demands, c, &mut rng);
+        let distance = clarke_wright_merge_vrp::improve_routes(&mut routes, challenge, d);
+        population.push((routes, distance));
+    }
+    let mut best = population
+        .iter()
+        .min_by_key(|(_, distance)| *distance)
+        .cloned()
+        .unwrap();
+
+    for _ in 0..MAX_GENERATIONS {
+        if best.1 <= max_total_distance {
+            break;
+        }
+
+        let first = tournament(&population, &mut rng);
+        let second = tournament(&population, &mut rng);
+        let mut child = split(
+            &order_crossover(
+                &giant_tour(&population[first].0),
+                &giant_tour(&population[second].0),
+                num_nodes,
+                &mut rng,
+            ),
+            depots,
+            d,
+            demands,
+            c,
+        );
+        if rng.gen::<f64>() < MUTATION_RATE {
+            mutate(&mut child, demands, c, &mut rng);
+        }
+        let child_distance = clarke_wright_merge_vrp::improve_routes(&mut child, challenge, d);
+
+        // Rejecting distances already present keeps the population from collapsing onto copies
+        // of one solution
+        if population
+            .iter()
+            .any(|(_, distance)| *distance == child_distance)
+        {
+            continue;
+        }
+        let (worst, _) = population
+            .iter()
+            .enumerate()
+            .max_by_key(|&(k, (_, distance))| (*distance, k))
+            .unwrap();
+        if child_distance < population[worst].1 {
+            if child_distance < best.1 {
+                best = (child.clone(), child_distance);
+            }
+            population[worst] = (child, child_distance);
+        }
+    }
+
+    best
+}
+
+// Nearest-neighbour routes from randomly chosen depots, where each step picks randomly among the
+// few closest customers that still fit, so every call gives a different start
+fn randomized_nearest_neighbor(
+    customers: &[usize],
+    depots: &[usize],
+    d: &FlatMatrix,
+    demands: &[i32],
+    c: i32,
+    rng: &mut StdRng,
+) -> Vec<Vec<usize>> {
+    let mut unvisited = customers.to_vec();
+    let mut routes = Vec::new();
+    while !unvisited.is_empty() {
+        let depot = depots[rng.gen_range(0..depots.len())];
+        let mut route = vec![depot];
+        let mut load = 0;
+        loop {
+            let last = *route.last().unwrap();
+            let mut candidates: Vec<usize> = (0..unvisited.len())
+                .filter(|&k| load + demands[unvisited[k]] <= c)
+                .collect();
+            if candidates.is_empty() {
+                break;
+            }
+            candidates.sort_unstable_by_key(|&k| (d.get(last, unvisited[k]), k));
+            let k = candidates[rng.gen_range(0..candidates.len().min(NEIGHBOR_CHOICES))];
+            let node = unvisited.swap_remove(k);
+            load += demands[node];
+            route.push(node);
+        }
+        route.push(depot);
+        routes.push(route);
+    }
+    routes
+}
+
+fn tournament(population: &[(Vec<Vec<usize>>, i64)], rng: &mut StdRng) -> usize {
+    (0..TOURNAMENT_SIZE)
+        .map(|_| rng.gen_range(0..population.len()))
+        .min_by_key(|&k| (population[k].1, k))
+        .unwrap()
+}
+
+// Customers in the order the routes visit them
+fn giant_tour(routes: &[Vec<usize>]) -> Vec<usize> {
+    routes
+        .iter()
+        .flat_map(|route| route[1..route.len() - 1].iter().cloned())
+        .collect()
+}
+
+// Order crossover (OX): the child keeps a random slice of the first parent in place and fills
+// the remaining positions with the other customers in the order the second parent visits them
+fn order_crossover(
+    first: &[usize],
+    second: &[usize],
+    num_nodes: usize,
+    rng: &mut StdRng,
+) -> Vec<usize> {
+    let n = first.len();
+    let start = rng.gen_range(0..n);
+    let end = rng.gen_range(start..n);
+
+    let mut in_slice = vec![false; num_nodes];
+    for &node in &first[start..=end] {
+        in_slice[node] = true;
+    }
+    let mut rest = second.iter().cloned().filter(|&node| !in_slice[node]);
+    (0..n)
+        .map(|k| {
+            if (start..=end).contains(&k) {
+                first[k]
+            } else {
+                rest.next().unwrap()
+            }
+        })
+        .collect()
+}
+
+// Repairs a giant tour into routes by starting a new route whenever the next customer would
+// exceed the capacity. Each route is served from the depot nearest its first customer
+fn split(
+    tour: &[usize],
+    depots: &[usize],
+    d: &FlatMatrix,
+    demands: &[i32],
+    c: i32,
+) -> Vec<Vec<usize>> {
+    let mut segments: Vec<Vec<usize>> = vec![Vec::new()];
+    let mut load = 0;
+    for &node in tour {
+        if load > 0 && load + demands[node] > c {
+            segments.push(Vec::new());
+            load = 0;
+        }
+        segments.last_mut().unwrap().push(node);
+        load += demands[node];
+    }
+
+    segments
+        .into_iter()
+        .map(|customers| {
+            let depot = *depots
+                .iter()
+                .min_by_key(|&&depot| d.get(depot, customers[0]))
+                .unwrap();
+            let mut route = vec![depot];
+            route.extend(customers);
+            route.push(depot);
+            route
+        })
+        .collect()
+}
+
+// Applies a random relocate or swap move between two routes, if capacity allows
+fn mutate(routes: &mut Vec<Vec<usize>>, demands: &[i32], c: i32, rng: &mut StdRng) {
+    let load = |route: &[usize]| route.iter().map(|&node| demands[node]).sum::<i32>();
+    let a = rng.gen_range(0..routes.len());
+    let b = rng.gen_range(0..routes.len());
+    let p = rng.gen_range(1..routes[a].len() - 1);
+    let u = routes[a][p];
+
+    if a == b || rng.gen_bool(0.5) {
+        // Relocate u to a random position of route b
+        if a != b && load(&routes[b]) + demands[u] > c {
+            return;
+        }
+        routes[a].remove(p);
+        let q = rng.gen_range(1..routes[b].len());
+        routes[b].insert(q, u);
+    } else {
+        // Swap u with a random customer of route b
+        let q = rng.gen_range(1..routes[b].len() - 1);
+        let v = routes[b][q];
+        if load(&routes[a]) - demands[u] + demands[v] > c
+            || load(&routes[b]) - demands[v] + demands[u] > c
+        {
+            return;
+        }
+        routes[a][p] = v;
+        routes[b][q] = u;
+    }
+    routes.retain(|route| route.len() > 2);
+}
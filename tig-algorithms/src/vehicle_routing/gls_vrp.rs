@@ -0,0 +1,143 @@
+/*!
+Copyright 2024 Crypti (PTY) LTD
+
+Licensed under the TIG Inbound Game License v1.0 or (at your option) any later
+version (the "License"); you may not use this file except in compliance with the
+License. You may obtain a copy of the License at
+
+https://github.com/tig-foundation/tig-monorepo/tree/main/docs/licenses
+
+Unless required by applicable law or agreed to in writing, software distributed
+under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR
+CONDITIONS OF ANY KIND, either express or implied. See the License for the specific
+language governing permissions and limitations under the License.
+*/
+
+use super::{
+    clarke_wright_merge_vrp, routes_total_distance, two_opt_route, Construction, FlatMatrix,
+};
+use tig_challenges::vehicle_routing::*;
+
+const MAX_PENALTY_ROUNDS: usize = 100;
+// Weight of a penalty relative to the average edge length of the first local optimum
+const ALPHA: f64 = 0.3;
+
+pub fn solve_challenge(challenge: &Challenge) -> anyhow::Result<Option<Solution>> {
+    solve_challenge_with_construction(challenge, Construction::ClarkeWright)
+}
+
+// Starts the search from the routes built by `construction`
+pub fn solve_challenge_with_construction(
+    challenge: &Challenge,
+    construction: Construction,
+) -> anyhow::Result<Option<Solution>> {
+    if !super::demands_fit_capacity(challenge) {
+        return Ok(None);
+    }
+
+    let d = &FlatMatrix::from_rows(&challenge.distance_matrix);
+    let routes = super::construct_initial_routes(challenge, d, construction)?;
+    let (routes, total_distance) = guided_local_search_from(challenge, d, routes);
+    Ok(super::finish_solution(challenge, &routes, total_distance))
+}
+
+pub fn solve_best_effort(challenge: &Challenge) -> anyhow::Result<Solution> {
+    super::check_demands_fit_capacity(challenge)?;
+    let (routes, _) = guided_local_search(challenge);
+    let routes = super::canonicalize_routes(&routes, &challenge.depots);
+    Ok(Solution { routes })
+}
+
+// Runs two-opt on the Clarke-Wright routes under a penalty-augmented cost, penalising the
+// edges with the highest utility whenever two-opt converges. Returns the best routes seen
+// together with their true (unpenalised) total distance
+pub fn guided_local_search(challenge: &Challenge) -> (Vec<Vec<usize>>, i64) {
+    let d = &FlatMatrix::from_rows(&challenge.distance_matrix);
+    let (routes, _) = clarke_wright_merge_vrp::build_routes(challenge, d);
+    guided_local_search_from(challenge, d, routes)
+}
+
+// Like `guided_local_search`, but starting from the given routes. `d` is the `FlatMatrix` of the
+// challenge's distances
+pub fn guided_local_search_from(
+    challenge: &Challenge,
+    d: &FlatMatrix,
+    mut routes: Vec<Vec<usize>>,
+) -> (Vec<Vec<usize>>, i64) {
+    let max_total_distance = challenge.max_total_distance as i64;
+    let n = challenge.difficulty.num_nodes;
+
+    let mut penalties = PenaltyMatrix::new(n);
+    let mut lambda = 0;
+
+    let mut best_routes = routes.clone();
+    let mut best_distance = routes_total_distance(&routes, d);
+
+    for round in 0..MAX_PENALTY_ROUNDS {
+        for route in routes.iter_mut() {
+            two_opt_route(route, |i, j| {
+                d.get(i, j) as i64 + lambda * penalties.get(i, j) as i64
+            });
+        }
+
+        // Fitness is always judged on the true distance
+        let total_distance = routes_total_distance(&routes, d);
+        if total_distance < best_distance {
+            best_distance = total_distance;
+            best_routes = routes.clone();
+        }
+        if best_distance <= max_total_distance {
+            break;
+        }
+
+        let edges: Vec<(usize, usize)> = routes
+            .iter()
+            .flat_map(|route| route.windows(2).map(|edge| (edge[0], edge[1])))
+            .collect();
+        if round == 0 {
+            lambda = ((ALPHA * total_distance as f64 / edges.len() as f64) as i64).max(1);
+        }
+
+        // Penalise the edges with the highest utility, cost / (1 + penalty)
+        let utilities: Vec<f64> = edges
+            .iter()
+            .map(|&(i, j)| d.get(i, j) as f64 / (1 + penalties.get(i, j)) as f64)
+            .collect();
+        let max_utility = utilities.iter().cloned().fold(f64::MIN, f64::max);
+        for (&(i, j), &utility) in edges.iter().zip(&utilities) {
+            if utility >= max_utility {
+                penalties.increment(i, j);
+            }
+        }
+    }
+
+    (best_routes, best_distance)
+}
+
+// Penalty counts for every edge, laid out like `FlatMatrix`. Kept symmetric so a reversed
+// segment carries its penalties with it
+pub struct PenaltyMatrix {
+    n: usize,
+    data: Vec<u32>,
+}
+
+impl PenaltyMatrix {
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            data: vec![0; n * n],
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, i: usize, j: usize) -> u32 {
+        self.data[i * self.n + j]
+    }
+
+    pub fn increment(&mut self, i: usize, j: usize) {
+        self.data[i * self.n + j] += 1;
+        if i != j {
+            self.data[j * self.n + i] += 1;
+        }
+    }
+}
@@ -1,8 +1,555 @@
+pub mod alns_vrp;
 pub mod clarke_wright_merge_vrp;
+pub mod genetic_vrp;
+pub mod gls_vrp;
+pub mod tabu_search_vrp;
+
+use anyhow::anyhow;
+use std::cmp::Reverse;
+use tig_challenges::vehicle_routing::{calc_baseline_routes, Challenge, Solution};
+
+// Common interface over the vehicle routing algorithms, so a harness can run and compare all of them
+pub trait VrpSolver {
+    fn name(&self) -> &str;
+    fn solve(&self, challenge: &Challenge) -> anyhow::Result<Option<Solution>>;
+}
+
+pub struct ClarkeWright;
+pub struct TabuSearch;
+pub struct GuidedLocalSearch;
+pub struct Alns;
+pub struct Genetic;
+
+impl VrpSolver for ClarkeWright {
+    fn name(&self) -> &str {
+        "clarke_wright"
+    }
+
+    fn solve(&self, challenge: &Challenge) -> anyhow::Result<Option<Solution>> {
+        clarke_wright_merge_vrp::solve_challenge(challenge)
+    }
+}
+
+impl VrpSolver for TabuSearch {
+    fn name(&self) -> &str {
+        "tabu_search"
+    }
+
+    fn solve(&self, challenge: &Challenge) -> anyhow::Result<Option<Solution>> {
+        tabu_search_vrp::solve_challenge(challenge)
+    }
+}
+
+impl VrpSolver for GuidedLocalSearch {
+    fn name(&self) -> &str {
+        "guided_local_search"
+    }
+
+    fn solve(&self, challenge: &Challenge) -> anyhow::Result<Option<Solution>> {
+        gls_vrp::solve_challenge(challenge)
+    }
+}
+
+impl VrpSolver for Alns {
+    fn name(&self) -> &str {
+        "alns"
+    }
+
+    fn solve(&self, challenge: &Challenge) -> anyhow::Result<Option<Solution>> {
+        alns_vrp::solve_challenge(challenge)
+    }
+}
+
+impl VrpSolver for Genetic {
+    fn name(&self) -> &str {
+        "genetic"
+    }
+
+    fn solve(&self, challenge: &Challenge) -> anyhow::Result<Option<Solution>> {
+        genetic_vrp::solve_challenge(challenge)
+    }
+}
+
+pub fn all_solvers() -> Vec<Box<dyn VrpSolver>> {
+    vec![
+        Box::new(ClarkeWright),
+        Box::new(TabuSearch),
+        Box::new(GuidedLocalSearch),
+        Box::new(Alns),
+        Box::new(Genetic),
+    ]
+}
+
+// Row-major copy of a square distance matrix. Avoids the pointer chase of
+// `Vec<Vec<i32>>` in hot loops, while `Challenge` keeps its nested layout
+pub struct FlatMatrix {
+    n: usize,
+    data: Vec<i32>,
+}
+
+impl FlatMatrix {
+    pub fn from_rows(rows: &[Vec<i32>]) -> Self {
+        let n = rows.len();
+        let mut data = Vec::with_capacity(n * n);
+        for row in rows {
+            debug_assert_eq!(row.len(), n, "distance matrix must be square");
+            data.extend_from_slice(row);
+        }
+        Self { n, data }
+    }
+
+    #[inline]
+    pub fn get(&self, i: usize, j: usize) -> i32 {
+        self.data[i * self.n + j]
+    }
+}
+
+// No routing exists if a single customer's demand exceeds the capacity of a vehicle
+pub fn demands_fit_capacity(challenge: &Challenge) -> bool {
+    challenge
+        .demands
+        .iter()
+        .all(|&demand| demand <= challenge.max_capacity)
+}
+
+// How the starting routes for a local search are built
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Construction {
+    // Clarke-Wright savings routes, see `clarke_wright_merge_vrp::build_routes`
+    ClarkeWright,
+    // Greedy nearest-neighbour routes, as used for the challenge baseline
+    NearestNeighbor,
+    // Regret-2 insertion, see `construct_regret_solution`
+    Regret,
+}
+
+// Only Clarke-Wright serves more than one depot, the other constructions route everything from
+// node 0 and fail if it is not the only depot
+pub fn construct_initial_routes(
+    challenge: &Challenge,
+    d: &FlatMatrix,
+    construction: Construction,
+) -> anyhow::Result<Vec<Vec<usize>>> {
+    if construction != Construction::ClarkeWright && challenge.depots != [0] {
+        return Err(anyhow!(
+            "{:?} construction only supports node 0 as the single depot",
+            construction
+        ));
+    }
+    match construction {
+        Construction::ClarkeWright => Ok(clarke_wright_merge_vrp::build_routes(challenge, d).0),
+        Construction::NearestNeighbor => calc_baseline_routes(
+            challenge.difficulty.num_nodes,
+            challenge.max_capacity,
+            &challenge.demands,
+            &challenge.distance_matrix,
+        ),
+        Construction::Regret => Ok(regret_routes(
+            challenge.difficulty.num_nodes,
+            challenge.max_capacity,
+            &challenge.demands,
+            d,
+        )),
+    }
+}
+
+// Builds routes from scratch by repeatedly inserting the unrouted customer with the largest gap
+// between its cheapest insertions into two different routes. Customers that fit in only one
+// route go first. A new route, seeded with the unrouted customer furthest from the depot, is
+// only opened once no remaining customer fits anywhere
+pub fn construct_regret_solution(
+    num_nodes: usize,
+    capacity: i32,
+    demands: &[i32],
+    distance_matrix: &[Vec<i32>],
+) -> Vec<Vec<usize>> {
+    regret_routes(
+        num_nodes,
+        capacity,
+        demands,
+        &FlatMatrix::from_rows(distance_matrix),
+    )
+}
+
+fn regret_routes(
+    num_nodes: usize,
+    capacity: i32,
+    demands: &[i32],
+    d: &FlatMatrix,
+) -> Vec<Vec<usize>> {
+    let mut routes: Vec<Vec<usize>> = Vec::new();
+    let mut loads: Vec<i32> = Vec::new();
+    let mut pending: Vec<usize> = (1..num_nodes).collect();
+
+    // Seed as many routes as the total demand needs with customers spread far apart, so routes
+    // grow in parallel rather than the first one sweeping up customers from everywhere
+    let total_demand: i32 = pending.iter().map(|&node| demands[node]).sum();
+    let num_seeds = ((total_demand + capacity - 1) / capacity.max(1)) as usize;
+    let mut seeds = vec![0];
+    while routes.len() < num_seeds && !pending.is_empty() {
+        let (k, _) = pending
+            .iter()
+            .enumerate()
+            .max_by_key(|&(k, &node)| {
+                let spread = seeds.iter().map(|&seed| d.get(seed, node)).min().unwrap();
+                (spread, Reverse(k))
+            })
+            .unwrap();
+        let node = pending.swap_remove(k);
+        seeds.push(node);
+        routes.push(vec![0, node, 0]);
+        loads.push(demands[node]);
+    }
+
+    while !pending.is_empty() {
+        // (regret, cost, index into pending, route, position) of the customer to insert next
+        let mut best: Option<(i64, i64, usize, usize, usize)> = None;
+        for (k, &node) in pending.iter().enumerate() {
+            // Cheapest insertion into each route with room for the customer
+            let mut options: Vec<(i64, usize, usize)> = routes
+                .iter()
+                .enumerate()
+                .filter(|&(r, _)| loads[r] + demands[node] <= capacity)
+                .map(|(r, route)| {
+                    (1..route.len())
+                        .map(|q| {
+                            let (x, y) = (route[q - 1], route[q]);
+                            ((d.get(x, node) + d.get(node, y) - d.get(x, y)) as i64, r, q)
+                        })
+                        .min()
+                        .unwrap()
+                })
+                .collect();
+            if options.is_empty() {
+                continue;
+            }
+            options.sort_unstable();
+            let (cost, r, q) = options[0];
+            let regret = match options.get(1) {
+                Some(second) => second.0 - cost,
+                None => i64::MAX,
+            };
+            match best {
+                Some((best_regret, best_cost, ..))
+                    if (best_regret, Reverse(best_cost)) >= (regret, Reverse(cost)) => {}
+                _ => best = Some((regret, cost, k, r, q)),
+            }
+        }
+
+        match best {
+            Some((_, _, k, r, q)) => {
+                let node = pending.swap_remove(k);
+                routes[r].insert(q, node);
+                loads[r] += demands[node];
+            }
+            None => {
+                let (k, _) = pending
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|&(k, &node)| (d.get(0, node) + d.get(node, 0), Reverse(k)))
+                    .unwrap();
+                let node = pending.swap_remove(k);
+                routes.push(vec![0, node, 0]);
+                loads.push(demands[node]);
+            }
+        }
+    }
+    routes
+}
+
+// Like `demands_fit_capacity`, but reports the first customer that cannot be served
+pub fn check_demands_fit_capacity(challenge: &Challenge) -> anyhow::Result<()> {
+    match challenge
+        .demands
+        .iter()
+        .position(|&demand| demand > challenge.max_capacity)
+    {
+        Some(node) => Err(anyhow!(
+            "Demand of customer {} ({}) exceeds vehicle capacity ({})",
+            node,
+            challenge.demands[node],
+            challenge.max_capacity
+        )),
+        None => Ok(()),
+    }
+}
+
+// Turns arbitrary routes back into a routing that serves every customer once within capacity.
+// Depot visits, unknown nodes and repeat visits are dropped, routes over capacity have customers
+// peeled off their end into new routes, and missing customers are inserted at their cheapest
+// feasible position, opening a new route if none fits. A customer whose demand alone exceeds
+// the capacity cannot be repaired and is left on a route of its own
+pub fn repair_solution(
+    routes: &[Vec<usize>],
+    demands: &[i32],
+    capacity: i32,
+    num_nodes: usize,
+    distance_matrix: &[Vec<i32>],
+) -> Vec<Vec<usize>> {
+    let d = &FlatMatrix::from_rows(distance_matrix);
+    let load = |route: &[usize]| route.iter().map(|&node| demands[node]).sum::<i32>();
+
+    // Customers only, in visiting order, each kept at its first visit
+    let mut visited = vec![false; num_nodes];
+    visited[0] = true;
+    let mut repaired: Vec<Vec<usize>> = Vec::with_capacity(routes.len());
+    for route in routes {
+        let mut customers = Vec::with_capacity(route.len());
+        for &node in route {
+            if node < num_nodes && !visited[node] {
+                visited[node] = true;
+                customers.push(node);
+            }
+        }
+        while load(&customers) > capacity && customers.len() > 1 {
+            let mut tail = Vec::new();
+            while load(&customers) > capacity && customers.len() > 1 {
+                tail.push(customers.pop().unwrap());
+            }
+            tail.reverse();
+            repaired.push(customers);
+            customers = tail;
+        }
+        repaired.push(customers);
+    }
+    repaired.retain(|customers| !customers.is_empty());
+    for customers in repaired.iter_mut() {
+        customers.insert(0, 0);
+        customers.push(0);
+    }
+
+    let mut loads: Vec<i32> = repaired.iter().map(|route| load(route)).collect();
+    for node in (1..num_nodes).filter(|&node| !visited[node]) {
+        let best = repaired
+            .iter()
+            .enumerate()
+            .filter(|&(r, _)| loads[r] + demands[node] <= capacity)
+            .flat_map(|(r, route)| {
+                (1..route.len()).map(move |q| {
+                    let (x, y) = (route[q - 1], route[q]);
+                    (d.get(x, node) + d.get(node, y) - d.get(x, y), r, q)
+                })
+            })
+            .min();
+        match best {
+            Some((_, r, q)) => {
+                repaired[r].insert(q, node);
+                loads[r] += demands[node];
+            }
+            None => {
+                repaired.push(vec![0, node, 0]);
+                loads.push(demands[node]);
+            }
+        }
+    }
+    repaired
+}
+
+// Puts routes into the form `verify_solution` expects, with each route's depot exactly once at
+// either end. Repeated depot visits are collapsed, a depot visit in the middle of a route splits
+// it in two and routes without customers are dropped. A route that neither starts nor ends at a
+// depot is served from the first one. Unlike `repair_solution`, customers are never moved
+pub fn canonicalize_routes(routes: &[Vec<usize>], depots: &[usize]) -> Vec<Vec<usize>> {
+    let mut canonical = Vec::new();
+    for route in routes {
+        let depot = [route.first(), route.last()]
+            .into_iter()
+            .flatten()
+            .find(|node| depots.contains(node))
+            .map_or(depots[0], |&depot| depot);
+        for customers in route.split(|&node| node == depot) {
+            if customers.is_empty() {
+                continue;
+            }
+            let mut canonical_route = Vec::with_capacity(customers.len() + 2);
+            canonical_route.push(depot);
+            canonical_route.extend_from_slice(customers);
+            canonical_route.push(depot);
+            canonical.push(canonical_route);
+        }
+    }
+    canonical
+}
+
+// Turns a solver's routes into its `solve_challenge` result, which is None when they miss
+// max_total_distance or max_routes. `total_distance` is the total the solver already tracks for
+// the routes. The solvers' `solve_best_effort` skip this check and only canonicalize, so they
+// return routes whenever every customer's demand fits in a vehicle
+pub fn finish_solution(
+    challenge: &Challenge,
+    routes: &[Vec<usize>],
+    total_distance: i64,
+) -> Option<Solution> {
+    let routes = canonicalize_routes(routes, &challenge.depots);
+    if total_distance > challenge.max_total_distance as i64
+        || challenge
+            .max_routes
+            .is_some_and(|max_routes| routes.len() > max_routes)
+    {
+        return None;
+    }
+    Some(Solution { routes })
+}
+
+// Total distance of routes that start and end at the depot
+pub fn routes_total_distance(routes: &[Vec<usize>], d: &FlatMatrix) -> i64 {
+    routes
+        .iter()
+        .flat_map(|route| route.windows(2))
+        .map(|edge| d.get(edge[0], edge[1]) as i64)
+        .sum()
+}
+
+// Reverses segments of a depot-anchored route until no reversal lowers its cost.
+// Returns whether the route was changed
+pub fn two_opt_route<F: Fn(usize, usize) -> i64>(route: &mut [usize], cost: F) -> bool {
+    // At least two customers are needed for a reversal to change anything
+    if route.len() < 4 {
+        return false;
+    }
+
+    let mut changed = false;
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in 1..route.len() - 2 {
+            for j in (i + 1)..route.len() - 1 {
+                if two_opt_delta(route, i, j, &cost) < 0 {
+                    route[i..=j].reverse();
+                    improved = true;
+                    changed = true;
+                }
+            }
+        }
+    }
+    changed
+}
+
+// Change in cost from reversing `route[i..=j]`. Edges (i - 1, i) and (j, j + 1) are replaced
+// with (i - 1, j) and (i, j + 1), and every edge inside the segment is traversed the other way,
+// which only cancels out when the cost is symmetric
+pub fn two_opt_delta<F: Fn(usize, usize) -> i64>(
+    route: &[usize],
+    i: usize,
+    j: usize,
+    cost: &F,
+) -> i64 {
+    let mut delta = cost(route[i - 1], route[j]) + cost(route[i], route[j + 1])
+        - cost(route[i - 1], route[i])
+        - cost(route[j], route[j + 1]);
+    for k in i..j {
+        delta += cost(route[k + 1], route[k]) - cost(route[k], route[k + 1]);
+    }
+    delta
+}
+
+// Maximum number of reversals chained by `lin_kernighan_route` from a single starting edge
+const LK_MAX_DEPTH: usize = 6;
+// Number of candidate new neighbours of the fixed node considered at each step of a chain
+const LK_BREADTH: usize = 5;
+
+// Variable-depth improvement of a depot-anchored route, a stronger drop-in for `two_opt_route`.
+// The route is first made two-optimal. Then, with the node before position `p` held fixed,
+// segments starting at `p` are reversed one after another, each time picking the best of the
+// closest candidate neighbours, as long as the gain before closing the route stays positive.
+// The best prefix of each chain is kept if it shortens the route. Returns whether the route
+// was changed
+pub fn lin_kernighan_route<F: Fn(usize, usize) -> i64>(route: &mut [usize], cost: F) -> bool {
+    let mut changed = two_opt_route(route, &cost);
+    if route.len() < 4 {
+        return changed;
+    }
+
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for p in 1..route.len() - 2 {
+            if let Some(reversals) = lin_kernighan_chain(route, p, &cost) {
+                for (i, j) in reversals {
+                    route[i..=j].reverse();
+                }
+                two_opt_route(route, &cost);
+                improved = true;
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+// Chains reversals of `route[p..=j]` and returns the prefix with the lowest total delta, if
+// that delta is negative. `route` is left unchanged
+fn lin_kernighan_chain<F: Fn(usize, usize) -> i64>(
+    route: &mut [usize],
+    p: usize,
+    cost: &F,
+) -> Option<Vec<(usize, usize)>> {
+    let fixed = route[p - 1];
+    let mut reversals = Vec::new();
+    let mut total_delta = 0;
+    let mut best: Option<(i64, usize)> = None;
+    // Nodes already brought next to `fixed` are not brought back, so chains cannot cycle
+    let mut used = vec![route[p]];
+
+    for _ in 0..LK_MAX_DEPTH {
+        let mut candidates: Vec<usize> = ((p + 1)..route.len() - 1)
+            .filter(|&j| !used.contains(&route[j]))
+            .collect();
+        candidates.sort_unstable_by_key(|&j| (cost(fixed, route[j]), j));
+        candidates.truncate(LK_BREADTH);
+        let Some((delta, j)) = candidates
+            .into_iter()
+            .map(|j| (two_opt_delta(route, p, j, cost), j))
+            .min()
+        else {
+            break;
+        };
+
+        route[p..=j].reverse();
+        reversals.push((p, j));
+        used.push(route[p]);
+        total_delta += delta;
+        if best.map_or(total_delta < 0, |(best_delta, _)| total_delta < best_delta) {
+            best = Some((total_delta, reversals.len()));
+        }
+        // The edge into the fixed node would be replaced by the next step, so it does not
+        // count against the gain of the chain so far
+        if cost(fixed, route[p]) - total_delta <= 0 {
+            break;
+        }
+    }
+
+    for &(i, j) in reversals.iter().rev() {
+        route[i..=j].reverse();
+    }
+    best.map(|(_, len)| reversals[..len].to_vec())
+}
+
+// How the local searches improve a single route
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteImprovement {
+    // Single reversals until none helps, see `two_opt_route`
+    TwoOpt,
+    // Chains of reversals, see `lin_kernighan_route`. Slower but never worse than two-opt
+    LinKernighan,
+}
+
+// Runs `improvement` on a depot-anchored route. Returns whether the route was changed
+pub fn improve_route<F: Fn(usize, usize) -> i64>(
+    route: &mut [usize],
+    cost: F,
+    improvement: RouteImprovement,
+) -> bool {
+    match improvement {
+        RouteImprovement::TwoOpt => two_opt_route(route, cost),
+        RouteImprovement::LinKernighan => lin_kernighan_route(route, cost),
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::{Duration, Instant};
     use tig_challenges::{vehicle_routing::*, *};
 
     #[test]
@@ -18,6 +565,7 @@ mod tests {
             // -- vehicle_routing --
             num_nodes: 40,
             better_than_baseline: 250,
+            demand_distribution: DemandDistribution::Uniform,
 
             // -- knapsack --
             // num_items: 50,
@@ -27,8 +575,8 @@ mod tests {
             // num_queries: 10,
             // better_than_baseline: 350,
         };
-        let seeds = [0; 8]; // change this to generate different instances
-        let challenge = Challenge::generate_instance(seeds, &difficulty).unwrap();
+        let seed = 0; // change this to generate different instances
+        let challenge = Challenge::generate_instance(seed, &difficulty).unwrap();
         match clarke_wright_merge_vrp::solve_challenge(&challenge) {
             Ok(Some(solution)) => match challenge.verify_solution(&solution) {
                 Ok(_) => println!("Valid solution"),
@@ -38,4 +586,767 @@ mod tests {
             Err(e) => println!("Algorithm error: {}", e),
         };
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_clarke_wright_merge_vrp_with_budget() {
+        let difficulty = Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 250,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        let start = Instant::now();
+        let solution =
+            clarke_wright_merge_vrp::solve_challenge_with_budget(&challenge, Duration::ZERO)
+                .unwrap()
+                .expect("a solution should be returned when the budget expires");
+        assert!(start.elapsed() < Duration::from_secs(1));
+        // No merges may have happened, so the routes can miss the target, but they must still be
+        // a valid routing
+        calc_routes_total_distance(
+            challenge.difficulty.num_nodes,
+            challenge.max_capacity,
+            &challenge.demands,
+            &challenge.distance_matrix,
+            &solution.routes,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_clarke_wright_merge_vrp_respects_max_total_distance() {
+        for better_than_baseline in [0, 250, 500] {
+            let difficulty = Difficulty {
+                num_nodes: 40,
+                better_than_baseline,
+                demand_distribution: DemandDistribution::Uniform,
+            };
+            let challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+            if let Some(solution) = clarke_wright_merge_vrp::solve_challenge(&challenge).unwrap() {
+                challenge.verify_solution(&solution).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_calculate_route_distance_does_not_overflow() {
+        let d = vec![
+            vec![0, 2_000_000_000, 2_000_000_000],
+            vec![2_000_000_000, 0, 2_000_000_000],
+            vec![2_000_000_000, 2_000_000_000, 0],
+        ];
+        let distance =
+            clarke_wright_merge_vrp::calculate_route_distance(&[1, 2], &FlatMatrix::from_rows(&d));
+        assert!(distance > 0);
+        assert_eq!(distance, 6_000_000_000);
+    }
+
+    #[test]
+    fn test_clarke_wright_merge_vrp_rejects_oversized_demand() {
+        let difficulty = Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 0,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let mut challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        challenge.demands[1] = challenge.max_capacity + 1;
+        assert!(!demands_fit_capacity(&challenge));
+        assert!(clarke_wright_merge_vrp::solve_challenge(&challenge)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_flat_matrix_matches_nested_matrix() {
+        let d: Vec<Vec<i32>> = (0..5)
+            .map(|i| (0..5).map(|j| i * 5 + j).collect())
+            .collect();
+        let flat = FlatMatrix::from_rows(&d);
+        for (i, row) in d.iter().enumerate() {
+            for (j, &distance) in row.iter().enumerate() {
+                assert_eq!(flat.get(i, j), distance);
+            }
+        }
+    }
+
+    #[test]
+    fn test_two_opt_delta_asymmetric() {
+        // Travelling towards a lower index costs extra, so reversing a segment is not free
+        let d: Vec<Vec<i32>> = (0..6)
+            .map(|i: i32| {
+                (0..6)
+                    .map(|j: i32| 10 * (i - j).abs() + if j < i { 7 * i } else { 0 })
+                    .collect()
+            })
+            .collect();
+        let d = FlatMatrix::from_rows(&d);
+        let cost = |i: usize, j: usize| d.get(i, j) as i64;
+        let route = vec![0, 1, 2, 3, 4, 5, 0];
+        let route_distance = routes_total_distance(std::slice::from_ref(&route), &d);
+
+        let mut mispredicted = false;
+        for i in 1..route.len() - 2 {
+            for j in (i + 1)..route.len() - 1 {
+                let mut reversed = route.clone();
+                reversed[i..=j].reverse();
+                let actual = routes_total_distance(&[reversed], &d) - route_distance;
+                assert_eq!(two_opt_delta(&route, i, j, &cost), actual);
+
+                let naive = cost(route[i - 1], route[j]) + cost(route[i], route[j + 1])
+                    - cost(route[i - 1], route[i])
+                    - cost(route[j], route[j + 1]);
+                mispredicted |= naive != actual;
+            }
+        }
+        assert!(mispredicted);
+
+        let mut improved = route.clone();
+        two_opt_route(&mut improved, cost);
+        assert!(routes_total_distance(&[improved], &d) <= route_distance);
+    }
+
+    #[test]
+    fn test_regret_construction_no_worse_than_nearest_neighbor() {
+        let difficulty = Difficulty {
+            num_nodes: 60,
+            better_than_baseline: 0,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let mut challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        let d = FlatMatrix::from_rows(&challenge.distance_matrix);
+        let two_opt_distance = |construction| {
+            let mut routes = construct_initial_routes(&challenge, &d, construction).unwrap();
+            for route in routes.iter_mut() {
+                two_opt_route(route, |i, j| d.get(i, j) as i64);
+            }
+            calc_routes_total_distance(
+                challenge.difficulty.num_nodes,
+                challenge.max_capacity,
+                &challenge.demands,
+                &challenge.distance_matrix,
+                &routes,
+            )
+            .unwrap()
+        };
+        assert!(
+            two_opt_distance(Construction::Regret)
+                <= two_opt_distance(Construction::NearestNeighbor)
+        );
+
+        // Guided local search can start from any construction
+        for construction in [
+            Construction::ClarkeWright,
+            Construction::NearestNeighbor,
+            Construction::Regret,
+        ] {
+            if let Some(solution) =
+                gls_vrp::solve_challenge_with_construction(&challenge, construction).unwrap()
+            {
+                challenge.verify_solution(&solution).unwrap();
+            }
+        }
+
+        challenge.depots = vec![0, 1];
+        assert!(construct_initial_routes(&challenge, &d, Construction::Regret).is_err());
+        assert!(construct_initial_routes(&challenge, &d, Construction::ClarkeWright).is_ok());
+    }
+
+    #[test]
+    fn test_solve_best_effort() {
+        // No routing comes anywhere near a tenth of the baseline distance
+        let difficulty = Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 900,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        assert!(clarke_wright_merge_vrp::solve_challenge(&challenge)
+            .unwrap()
+            .is_none());
+
+        for solve_best_effort in [
+            clarke_wright_merge_vrp::solve_best_effort,
+            tabu_search_vrp::solve_best_effort,
+            gls_vrp::solve_best_effort,
+            alns_vrp::solve_best_effort,
+            genetic_vrp::solve_best_effort,
+        ] {
+            let solution = solve_best_effort(&challenge).unwrap();
+            // Every customer is served within capacity, only the distance target is missed
+            calc_routes_total_distance(
+                challenge.difficulty.num_nodes,
+                challenge.max_capacity,
+                &challenge.demands,
+                &challenge.distance_matrix,
+                &solution.routes,
+            )
+            .unwrap();
+            let error = challenge.verify_solution(&solution).unwrap_err();
+            assert!(matches!(
+                error.downcast_ref::<VerificationError>(),
+                Some(VerificationError::TotalDistanceExceeded { .. })
+            ));
+        }
+
+        let mut challenge = challenge;
+        challenge.demands[1] = challenge.max_capacity + 1;
+        assert!(clarke_wright_merge_vrp::solve_best_effort(&challenge).is_err());
+    }
+
+    #[test]
+    fn test_lin_kernighan_no_worse_than_two_opt() {
+        let difficulty = Difficulty {
+            num_nodes: 60,
+            better_than_baseline: 0,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        let d = FlatMatrix::from_rows(&challenge.distance_matrix);
+        let cost = |i: usize, j: usize| d.get(i, j) as i64;
+
+        // A single route through every node in index order, ignoring capacity
+        let route: Vec<usize> = (0..60).chain([0]).collect();
+        let mut two_opt = route.clone();
+        two_opt_route(&mut two_opt, cost);
+        let mut lin_kernighan = route.clone();
+        assert!(lin_kernighan_route(&mut lin_kernighan, cost));
+
+        assert_eq!((lin_kernighan[0], lin_kernighan[60]), (0, 0));
+        let mut visited = lin_kernighan.clone();
+        visited.sort_unstable();
+        assert_eq!(visited[1..], route[..60]);
+        assert!(
+            routes_total_distance(&[lin_kernighan], &d) <= routes_total_distance(&[two_opt], &d)
+        );
+
+        // No single reversal shortens this route, but a chain of them does
+        let d = FlatMatrix::from_rows(&[
+            vec![0, 10, 16, 18, 6, 9],
+            vec![10, 0, 19, 10, 16, 15],
+            vec![16, 19, 0, 7, 15, 11],
+            vec![18, 10, 7, 0, 4, 15],
+            vec![6, 16, 15, 4, 0, 8],
+            vec![9, 15, 11, 15, 8, 0],
+        ]);
+        let cost = |i: usize, j: usize| d.get(i, j) as i64;
+        let mut route = vec![0, 1, 2, 3, 4, 5, 0];
+        assert!(!two_opt_route(&mut route, cost));
+        assert_eq!(routes_total_distance(std::slice::from_ref(&route), &d), 57);
+        assert!(lin_kernighan_route(&mut route, cost));
+        assert!(routes_total_distance(std::slice::from_ref(&route), &d) < 57);
+    }
+
+    #[test]
+    fn test_repair_solution() {
+        let difficulty = Difficulty {
+            num_nodes: 30,
+            better_than_baseline: 0,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let mut challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        challenge.max_total_distance = i32::MAX;
+        let d = FlatMatrix::from_rows(&challenge.distance_matrix);
+        let (mut routes, _) = clarke_wright_merge_vrp::build_routes(&challenge, &d);
+
+        // Drop a customer, overload a route by joining two, and visit a customer twice
+        let missing = routes[0].remove(1);
+        let second = routes.remove(1);
+        routes[0].pop();
+        routes[0].extend(&second[1..]);
+        let duplicate = routes[1][1];
+        routes[0].insert(1, duplicate);
+        let broken = Solution {
+            routes: routes.clone(),
+        };
+        assert!(challenge.verify_solution(&broken).is_err());
+
+        let repaired = repair_solution(
+            &routes,
+            &challenge.demands,
+            challenge.max_capacity,
+            challenge.difficulty.num_nodes,
+            &challenge.distance_matrix,
+        );
+        assert!(repaired.iter().any(|route| route.contains(&missing)));
+        challenge
+            .verify_solution(&Solution { routes: repaired })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_canonicalize_routes() {
+        let difficulty = Difficulty {
+            num_nodes: 20,
+            better_than_baseline: 0,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        let routes = calc_baseline_routes(
+            difficulty.num_nodes,
+            challenge.max_capacity,
+            &challenge.demands,
+            &challenge.distance_matrix,
+        )
+        .unwrap();
+        assert_eq!(canonicalize_routes(&routes, &challenge.depots), routes);
+
+        let mut malformed = routes.clone();
+        // Missing trailing depot
+        malformed[0].pop();
+        // Doubled depot at both ends
+        malformed[1].insert(0, 0);
+        malformed[1].push(0);
+        // Missing leading depot
+        malformed[2].remove(0);
+        malformed.push(vec![0, 0]);
+        malformed.push(vec![]);
+        let canonical = canonicalize_routes(&malformed, &challenge.depots);
+        assert_eq!(canonical, routes);
+        challenge
+            .verify_solution(&Solution { routes: canonical })
+            .unwrap();
+
+        // A depot visit in the middle of a route splits it
+        let mut joined = routes.clone();
+        let second = joined.remove(1);
+        joined[0].extend_from_slice(&second[1..]);
+        let canonical = canonicalize_routes(&joined, &challenge.depots);
+        assert_eq!(canonical.len(), routes.len());
+        challenge
+            .verify_solution(&Solution { routes: canonical })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_finish_solution() {
+        let difficulty = Difficulty {
+            num_nodes: 20,
+            better_than_baseline: 0,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let mut challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        let routes = calc_baseline_routes(
+            difficulty.num_nodes,
+            challenge.max_capacity,
+            &challenge.demands,
+            &challenge.distance_matrix,
+        )
+        .unwrap();
+        let d = FlatMatrix::from_rows(&challenge.distance_matrix);
+        let total_distance = routes_total_distance(&routes, &d);
+
+        // Routes without customers are dropped before counting them against max_routes
+        let mut padded = routes.clone();
+        padded.push(vec![0, 0]);
+        challenge.max_total_distance = total_distance as i32;
+        challenge.max_routes = Some(routes.len());
+        let solution = finish_solution(&challenge, &padded, total_distance).unwrap();
+        assert_eq!(solution.routes, routes);
+
+        challenge.max_routes = Some(routes.len() - 1);
+        assert!(finish_solution(&challenge, &routes, total_distance).is_none());
+
+        challenge.max_routes = None;
+        challenge.max_total_distance = total_distance as i32 - 1;
+        assert!(finish_solution(&challenge, &routes, total_distance).is_none());
+    }
+
+    #[test]
+    fn test_tabu_search_vrp() {
+        for better_than_baseline in [0, 250] {
+            let difficulty = Difficulty {
+                num_nodes: 40,
+                better_than_baseline,
+                demand_distribution: DemandDistribution::Uniform,
+            };
+            let challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+            match tabu_search_vrp::solve_challenge(&challenge).unwrap() {
+                Some(solution) => challenge.verify_solution(&solution).unwrap(),
+                None => assert_ne!(better_than_baseline, 0, "baseline should be beaten"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_gls_vrp_no_worse_than_two_opt() {
+        let difficulty = Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 250,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        let d = FlatMatrix::from_rows(&challenge.distance_matrix);
+
+        let (mut routes, _) = clarke_wright_merge_vrp::build_routes(&challenge, &d);
+        for route in routes.iter_mut() {
+            two_opt_route(route, |i, j| d.get(i, j) as i64);
+        }
+        let two_opt_distance = routes_total_distance(&routes, &d);
+
+        let (gls_routes, gls_distance) = gls_vrp::guided_local_search(&challenge);
+        assert_eq!(gls_distance, routes_total_distance(&gls_routes, &d));
+        assert!(gls_distance <= two_opt_distance);
+        calc_routes_total_distance(
+            challenge.difficulty.num_nodes,
+            challenge.max_capacity,
+            &challenge.demands,
+            &challenge.distance_matrix,
+            &gls_routes,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_clarke_wright_merge_vrp_respects_max_routes() {
+        let difficulty = Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 0,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let mut challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        challenge.max_total_distance = i32::MAX;
+        let d = FlatMatrix::from_rows(&challenge.distance_matrix);
+        let (routes, _) = clarke_wright_merge_vrp::build_routes(&challenge, &d);
+        for max_routes in [1, routes.len(), challenge.difficulty.num_nodes] {
+            challenge.max_routes = Some(max_routes);
+            match clarke_wright_merge_vrp::solve_challenge(&challenge).unwrap() {
+                Some(solution) => {
+                    assert!(solution.routes.len() <= max_routes);
+                    challenge.verify_solution(&solution).unwrap();
+                }
+                // A single vehicle can never serve every customer
+                None => assert_eq!(max_routes, 1),
+            }
+        }
+    }
+
+    #[test]
+    fn test_clarke_wright_merge_vrp_max_routes_multi_depot() {
+        // Depots 0 and 1 each serve two customers, and joining a depot's customers costs more
+        // than serving them separately, so merges only happen to meet max_routes
+        let challenge = Challenge {
+            seed: 0,
+            difficulty: Difficulty {
+                num_nodes: 6,
+                better_than_baseline: 0,
+                demand_distribution: DemandDistribution::Uniform,
+            },
+            demands: vec![0, 0, 1, 1, 1, 1],
+            distance_matrix: vec![
+                vec![0, 100, 10, 10, 100, 100],
+                vec![100, 0, 100, 100, 10, 10],
+                vec![10, 100, 0, 30, 100, 100],
+                vec![10, 100, 30, 0, 100, 100],
+                vec![100, 10, 100, 100, 0, 30],
+                vec![100, 10, 100, 100, 30, 0],
+            ],
+            max_total_distance: 1000,
+            max_capacity: 10,
+            // Neither depot alone has more routes than this, but both together do
+            max_routes: Some(3),
+            depots: vec![0, 1],
+        };
+        let solution = clarke_wright_merge_vrp::solve_challenge(&challenge)
+            .unwrap()
+            .unwrap();
+        assert_eq!(solution.routes.len(), 3);
+        challenge.verify_solution(&solution).unwrap();
+    }
+
+    #[test]
+    fn test_alns_vrp() {
+        let difficulty = Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 250,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        let d = FlatMatrix::from_rows(&challenge.distance_matrix);
+
+        let (_, clarke_wright_distance) = clarke_wright_merge_vrp::build_routes(&challenge, &d);
+        let (routes, alns_distance) = alns_vrp::adaptive_large_neighborhood_search(&challenge);
+        assert_eq!(alns_distance, routes_total_distance(&routes, &d));
+        assert!(alns_distance <= clarke_wright_distance);
+        calc_routes_total_distance(
+            challenge.difficulty.num_nodes,
+            challenge.max_capacity,
+            &challenge.demands,
+            &challenge.distance_matrix,
+            &routes,
+        )
+        .unwrap();
+
+        if let Some(solution) = alns_vrp::solve_challenge(&challenge).unwrap() {
+            challenge.verify_solution(&solution).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_genetic_vrp() {
+        let difficulty = Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 250,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        let d = FlatMatrix::from_rows(&challenge.distance_matrix);
+
+        let (_, clarke_wright_distance) = clarke_wright_merge_vrp::build_routes(&challenge, &d);
+        let (routes, genetic_distance) = genetic_vrp::genetic_algorithm(&challenge);
+        assert_eq!(genetic_distance, routes_total_distance(&routes, &d));
+        assert!(genetic_distance <= clarke_wright_distance);
+        calc_routes_total_distance(
+            challenge.difficulty.num_nodes,
+            challenge.max_capacity,
+            &challenge.demands,
+            &challenge.distance_matrix,
+            &routes,
+        )
+        .unwrap();
+
+        // The search is seeded from the challenge, so it is reproducible
+        assert_eq!(
+            genetic_vrp::genetic_algorithm(&challenge),
+            (routes, genetic_distance)
+        );
+
+        if let Some(solution) = genetic_vrp::solve_challenge(&challenge).unwrap() {
+            challenge.verify_solution(&solution).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_genetic_vrp_multi_depot() {
+        let difficulty = Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 250,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let mut challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        let depot = (1..difficulty.num_nodes)
+            .max_by_key(|&node| challenge.distance_matrix[0][node])
+            .unwrap();
+        challenge.demands[depot] = 0;
+        challenge.depots = vec![0, depot];
+
+        let d = FlatMatrix::from_rows(&challenge.distance_matrix);
+        // A target just below the improved Clarke-Wright routes, which seed the population, so
+        // the search cannot stop before crossover runs
+        let (mut routes, _) = clarke_wright_merge_vrp::build_routes(&challenge, &d);
+        let seed_distance = clarke_wright_merge_vrp::improve_routes(&mut routes, &challenge, &d);
+        challenge.max_total_distance = seed_distance as i32 - 1;
+        assert!(seed_distance > challenge.max_total_distance as i64);
+
+        let solution = genetic_vrp::solve_best_effort(&challenge).unwrap();
+        for route in solution.routes.iter() {
+            assert!(challenge.depots.contains(&route[0]));
+            assert_eq!(route[0], *route.last().unwrap());
+        }
+        calc_multi_depot_routes_total_distance(
+            challenge.difficulty.num_nodes,
+            challenge.max_capacity,
+            &challenge.demands,
+            &challenge.distance_matrix,
+            &challenge.depots,
+            &solution.routes,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_all_solvers() {
+        let difficulty = Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 100,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+
+        // Two depots, neither of them node 0, which becomes a customer without demand
+        let mut multi_depot = Challenge::generate_instance(0, &difficulty).unwrap();
+        let mut by_distance: Vec<usize> = (1..difficulty.num_nodes).collect();
+        by_distance.sort_unstable_by_key(|&node| Reverse(multi_depot.distance_matrix[0][node]));
+        multi_depot.depots = by_distance[..2].to_vec();
+        for &depot in multi_depot.depots.iter() {
+            multi_depot.demands[depot] = 0;
+        }
+
+        for challenge in [&challenge, &multi_depot] {
+            for solver in all_solvers() {
+                match solver.solve(challenge) {
+                    Ok(Some(solution)) => {
+                        if let Err(e) = challenge.verify_solution(&solution) {
+                            panic!("{} returned an invalid solution: {}", solver.name(), e);
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => panic!("{} failed: {}", solver.name(), e),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_clarke_wright_merge_vrp_lambda_sweep() {
+        let difficulty = Difficulty {
+            num_nodes: 60,
+            better_than_baseline: 0,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        // Tuning lambda pays off on most instances but not every one, so sum over a few seeds
+        let mut default_distance = 0;
+        let mut tuned_distance = 0;
+        for seed in 0..5 {
+            let mut challenge = Challenge::generate_instance(seed, &difficulty).unwrap();
+            challenge.max_total_distance = i32::MAX;
+            let d = FlatMatrix::from_rows(&challenge.distance_matrix);
+            let distance = |lambda| {
+                let solution =
+                    clarke_wright_merge_vrp::solve_challenge_with_lambda(&challenge, lambda)
+                        .unwrap()
+                        .unwrap();
+                challenge.verify_solution(&solution).unwrap();
+                routes_total_distance(&solution.routes, &d)
+            };
+            let default = distance(1.0);
+            assert_eq!(
+                clarke_wright_merge_vrp::solve_challenge(&challenge).unwrap(),
+                clarke_wright_merge_vrp::solve_challenge_with_lambda(&challenge, 1.0).unwrap()
+            );
+            default_distance += default;
+            tuned_distance += [0.6, 0.8, 1.2, 1.4, 1.6, 1.8, 2.0]
+                .into_iter()
+                .map(distance)
+                .min()
+                .unwrap();
+        }
+        assert!(tuned_distance <= default_distance);
+    }
+
+    #[test]
+    fn test_clarke_wright_merge_vrp_multi_depot() {
+        let difficulty = Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 0,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let mut challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        challenge.max_total_distance = i32::MAX;
+        let single_depot = clarke_wright_merge_vrp::solve_challenge(&challenge).unwrap();
+
+        // Turn the customer furthest from the centre into a second depot
+        let depot = (1..difficulty.num_nodes)
+            .max_by_key(|&node| challenge.distance_matrix[0][node])
+            .unwrap();
+        challenge.demands[depot] = 0;
+        challenge.depots = vec![0, depot];
+
+        let solution = clarke_wright_merge_vrp::solve_challenge(&challenge)
+            .unwrap()
+            .unwrap();
+        for route in solution.routes.iter() {
+            assert!(challenge.depots.contains(&route[0]));
+            assert_eq!(route[0], *route.last().unwrap());
+        }
+        challenge.verify_solution(&solution).unwrap();
+        assert_ne!(Some(solution), single_depot);
+    }
+
+    #[test]
+    fn test_clarke_wright_merge_vrp_ties_are_deterministic() {
+        // Every pair of customers has the same saving
+        let num_nodes = 7;
+        let challenge = Challenge {
+            seed: 0,
+            difficulty: Difficulty {
+                num_nodes,
+                better_than_baseline: 0,
+                demand_distribution: DemandDistribution::Uniform,
+            },
+            demands: vec![0, 1, 1, 1, 1, 1, 1],
+            distance_matrix: (0..num_nodes)
+                .map(|i| {
+                    (0..num_nodes)
+                        .map(|j| if i == j { 0 } else { 10 })
+                        .collect()
+                })
+                .collect(),
+            max_total_distance: 1000,
+            max_capacity: 2,
+            max_routes: None,
+            depots: vec![0],
+        };
+
+        let solve = || {
+            clarke_wright_merge_vrp::solve_challenge(&challenge)
+                .unwrap()
+                .unwrap()
+                .routes
+        };
+        let routes = solve();
+        for _ in 0..5 {
+            assert_eq!(solve(), routes);
+        }
+        // Ties are merged in order of the node pair
+        assert_eq!(
+            routes,
+            vec![vec![0, 1, 2, 0], vec![0, 3, 4, 0], vec![0, 5, 6, 0]]
+        );
+    }
+
+    #[test]
+    fn test_clarke_wright_merge_vrp_post_processing() {
+        let difficulty = Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 0,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let mut challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        let d = FlatMatrix::from_rows(&challenge.distance_matrix);
+        let (routes, raw_distance) = clarke_wright_merge_vrp::build_routes(&challenge, &d);
+
+        let mut improved = routes.clone();
+        let improved_distance =
+            clarke_wright_merge_vrp::improve_routes(&mut improved, &challenge, &d);
+        assert!(improved_distance <= raw_distance);
+
+        // The raw routes only just meet this, so the post-processed ones must too
+        challenge.max_total_distance = raw_distance as i32;
+        let solution = clarke_wright_merge_vrp::solve_challenge(&challenge)
+            .unwrap()
+            .unwrap();
+        assert_eq!(solution.routes, improved);
+        challenge.verify_solution(&solution).unwrap();
+    }
+
+    #[test]
+    fn test_clarke_wright_merge_vrp_lin_kernighan_post_processing() {
+        let difficulty = Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 0,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let mut challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        let d = FlatMatrix::from_rows(&challenge.distance_matrix);
+        let (routes, raw_distance) = clarke_wright_merge_vrp::build_routes(&challenge, &d);
+
+        let mut improved = routes.clone();
+        let improved_distance = clarke_wright_merge_vrp::improve_routes_with(
+            &mut improved,
+            &challenge,
+            &d,
+            RouteImprovement::LinKernighan,
+        );
+        assert!(improved_distance <= raw_distance);
+
+        challenge.max_total_distance = raw_distance as i32;
+        let solution = clarke_wright_merge_vrp::solve_challenge_with_improvement(
+            &challenge,
+            RouteImprovement::LinKernighan,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(solution.routes, improved);
+        challenge.verify_solution(&solution).unwrap();
+    }
+}
@@ -0,0 +1,183 @@
+/*!
+Copyright 2024 Crypti (PTY) LTD
+
+Licensed under the TIG Inbound Game License v1.0 or (at your option) any later
+version (the "License"); you may not use this file except in compliance with the
+License. You may obtain a copy of the License at
+
+https://github.com/tig-foundation/tig-monorepo/tree/main/docs/licenses
+
+Unless required by applicable law or agreed to in writing, software distributed
+under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR
+CONDITIONS OF ANY KIND, either express or implied. See the License for the specific
+language governing permissions and limitations under the License.
+*/
+
+use super::{clarke_wright_merge_vrp, FlatMatrix};
+use tig_challenges::vehicle_routing::*;
+
+const MAX_ITERATIONS: usize = 1000;
+const TABU_TENURE: usize = 10;
+
+pub fn solve_challenge(challenge: &Challenge) -> anyhow::Result<Option<Solution>> {
+    solve_challenge_with_tenure(challenge, TABU_TENURE)
+}
+
+// `tenure` is the number of iterations a node is forbidden from moving back into a route it left
+pub fn solve_challenge_with_tenure(
+    challenge: &Challenge,
+    tenure: usize,
+) -> anyhow::Result<Option<Solution>> {
+    if !super::demands_fit_capacity(challenge) {
+        return Ok(None);
+    }
+
+    let (routes, total_distance) = tabu_search(challenge, tenure);
+    Ok(super::finish_solution(challenge, &routes, total_distance))
+}
+
+pub fn solve_best_effort(challenge: &Challenge) -> anyhow::Result<Solution> {
+    super::check_demands_fit_capacity(challenge)?;
+    let (routes, _) = tabu_search(challenge, TABU_TENURE);
+    let routes = super::canonicalize_routes(&routes, &challenge.depots);
+    Ok(Solution { routes })
+}
+
+// Relocate and swap moves between routes, starting from the Clarke-Wright routes. Returns the
+// best routes seen together with their total distance
+pub fn tabu_search(challenge: &Challenge, tenure: usize) -> (Vec<Vec<usize>>, i64) {
+    let d = &FlatMatrix::from_rows(&challenge.distance_matrix);
+    let demands = &challenge.demands;
+    let c = challenge.max_capacity;
+    let max_total_distance = challenge.max_total_distance as i64;
+    let n = challenge.difficulty.num_nodes;
+
+    let (mut routes, mut total_distance) = clarke_wright_merge_vrp::build_routes(challenge, d);
+    let num_routes = routes.len();
+    let mut loads: Vec<i32> = routes
+        .iter()
+        .map(|route| route.iter().map(|&node| demands[node]).sum())
+        .collect();
+    let mut best_routes = routes.clone();
+    let mut best_distance = total_distance;
+
+    // Iteration until which moving node `u` into route `r` is tabu, indexed by `u * num_routes + r`
+    let mut tabu_until = vec![0; n * num_routes];
+
+    for iteration in 0..MAX_ITERATIONS {
+        if best_distance <= max_total_distance {
+            break;
+        }
+
+        let is_admissible =
+            |delta: i64, tabu: bool| !tabu || total_distance + delta < best_distance;
+        let mut best_move: Option<Move> = None;
+        let mut best_delta = i64::MAX;
+
+        for a in 0..num_routes {
+            for p in 1..routes[a].len() - 1 {
+                let u = routes[a][p];
+                let (prev_u, next_u) = (routes[a][p - 1], routes[a][p + 1]);
+                let remove_u = d.get(prev_u, next_u) - d.get(prev_u, u) - d.get(u, next_u);
+
+                for b in 0..num_routes {
+                    if a == b {
+                        continue;
+                    }
+
+                    // Relocate u into route b, between positions q - 1 and q
+                    if loads[b] + demands[u] <= c {
+                        let tabu = tabu_until[u * num_routes + b] > iteration;
+                        for q in 1..routes[b].len() {
+                            let (x, y) = (routes[b][q - 1], routes[b][q]);
+                            let delta = (remove_u + d.get(x, u) + d.get(u, y) - d.get(x, y)) as i64;
+                            if delta < best_delta && is_admissible(delta, tabu) {
+                                best_move = Some(Move::Relocate { a, p, b, q });
+                                best_delta = delta;
+                            }
+                        }
+                    }
+
+                    // Swap u with node v at position q of route b. Each pair is only visited once
+                    if b < a {
+                        continue;
+                    }
+                    for q in 1..routes[b].len() - 1 {
+                        let v = routes[b][q];
+                        if loads[a] - demands[u] + demands[v] > c
+                            || loads[b] - demands[v] + demands[u] > c
+                        {
+                            continue;
+                        }
+                        let (prev_v, next_v) = (routes[b][q - 1], routes[b][q + 1]);
+                        let delta = (d.get(prev_u, v) + d.get(v, next_u)
+                            - d.get(prev_u, u)
+                            - d.get(u, next_u)
+                            + d.get(prev_v, u)
+                            + d.get(u, next_v)
+                            - d.get(prev_v, v)
+                            - d.get(v, next_v)) as i64;
+                        let tabu = tabu_until[u * num_routes + b] > iteration
+                            || tabu_until[v * num_routes + a] > iteration;
+                        if delta < best_delta && is_admissible(delta, tabu) {
+                            best_move = Some(Move::Swap { a, p, b, q });
+                            best_delta = delta;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Every move is tabu and none satisfies the aspiration criterion
+        let Some(m) = best_move else {
+            break;
+        };
+
+        match m {
+            Move::Relocate { a, p, b, q } => {
+                let u = routes[a].remove(p);
+                routes[b].insert(q, u);
+                loads[a] -= demands[u];
+                loads[b] += demands[u];
+                tabu_until[u * num_routes + a] = iteration + tenure;
+            }
+            Move::Swap { a, p, b, q } => {
+                let (u, v) = (routes[a][p], routes[b][q]);
+                routes[a][p] = v;
+                routes[b][q] = u;
+                loads[a] += demands[v] - demands[u];
+                loads[b] += demands[u] - demands[v];
+                tabu_until[u * num_routes + a] = iteration + tenure;
+                tabu_until[v * num_routes + b] = iteration + tenure;
+            }
+        }
+        total_distance += best_delta;
+
+        if total_distance < best_distance {
+            best_distance = total_distance;
+            best_routes = routes.clone();
+        }
+    }
+
+    // Routes emptied by relocations are dropped
+    best_routes.retain(|route| route.len() > 2);
+    (best_routes, best_distance)
+}
+
+#[derive(Clone, Copy)]
+enum Move {
+    // Move the node at position `p` of route `a` to position `q` of route `b`
+    Relocate {
+        a: usize,
+        p: usize,
+        b: usize,
+        q: usize,
+    },
+    // Exchange the node at position `p` of route `a` with the node at position `q` of route `b`
+    Swap {
+        a: usize,
+        p: usize,
+        b: usize,
+        q: usize,
+    },
+}
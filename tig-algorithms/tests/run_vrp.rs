@@ -0,0 +1,39 @@
+use std::process::Command;
+
+fn run_vrp(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_run_vrp"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_run_vrp_valid_solution() {
+    let output = run_vrp(&[
+        "--algorithm",
+        "clarke_wright",
+        "--num-nodes",
+        "20",
+        "--better-than-baseline",
+        "0",
+        "--seed",
+        "0",
+    ]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("Total distance: "));
+    assert!(stdout.contains("Valid solution"));
+}
+
+#[test]
+fn test_run_vrp_rejects_unknown_algorithm() {
+    let output = run_vrp(&[
+        "--algorithm",
+        "unknown",
+        "--num-nodes",
+        "20",
+        "--better-than-baseline",
+        "0",
+    ]);
+    assert!(!output.status.success());
+}
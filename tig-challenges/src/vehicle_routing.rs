@@ -2,11 +2,31 @@ use anyhow::{anyhow, Result};
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use serde_json::{from_value, Map, Value};
+use std::fmt;
 
-#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
 pub struct Difficulty {
     pub num_nodes: usize,
     pub better_than_baseline: u32,
+    // Not part of the difficulty array, which the protocol fixes at [num_nodes,
+    // better_than_baseline]. `from_arr` always gives `Uniform` and `to_arr` drops this field, so
+    // `generate_instance_from_vec` (as used by tig-worker) only generates uniform demands. The
+    // other distributions are only reachable by building a `Difficulty` directly
+    #[serde(default)]
+    pub demand_distribution: DemandDistribution,
+}
+
+// How `generate_instance` draws customer demands. Every distribution stays well below the
+// vehicle capacity of 100, so each customer can always be served on its own
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum DemandDistribution {
+    // Every demand drawn from 15..30
+    #[default]
+    Uniform,
+    // Customers near a few randomly chosen hotspots have heavy demands, the rest light ones
+    Clustered,
+    // Each customer is independently light or heavy with equal probability
+    Bimodal,
 }
 
 impl crate::DifficultyTrait<2> for Difficulty {
@@ -14,6 +34,8 @@ impl crate::DifficultyTrait<2> for Difficulty {
         Self {
             num_nodes: arr[0] as usize,
             better_than_baseline: arr[1] as u32,
+            // The array has no slot for the distribution, see `Difficulty::demand_distribution`
+            demand_distribution: DemandDistribution::default(),
         }
     }
 
@@ -22,7 +44,7 @@ impl crate::DifficultyTrait<2> for Difficulty {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Solution {
     pub routes: Vec<Vec<usize>>,
 }
@@ -37,7 +59,75 @@ impl TryFrom<Map<String, Value>> for Solution {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+// Summary statistics of a solution, for analysing results. Loads are the summed demands of
+// the customers on a route
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct SolutionMetrics {
+    pub num_routes: usize,
+    pub total_distance: i64,
+    pub max_route_load: i32,
+    pub min_route_load: i32,
+    pub total_demand_served: i32,
+}
+
+impl Solution {
+    // Computed directly from the routes without validating them, so routes that would fail
+    // `verify_solution` still get metrics. Loads are 0 when there are no routes
+    pub fn metrics(&self, challenge: &Challenge) -> SolutionMetrics {
+        let loads: Vec<i32> = self
+            .routes
+            .iter()
+            .map(|route| route.iter().map(|&node| challenge.demands[node]).sum())
+            .collect();
+        let total_distance = self
+            .routes
+            .iter()
+            .flat_map(|route| route.windows(2))
+            .map(|edge| challenge.distance_matrix[edge[0]][edge[1]] as i64)
+            .sum();
+
+        SolutionMetrics {
+            num_routes: self.routes.len(),
+            total_distance,
+            max_route_load: loads.iter().cloned().max().unwrap_or(0),
+            min_route_load: loads.iter().cloned().min().unwrap_or(0),
+            total_demand_served: loads.iter().sum(),
+        }
+    }
+
+    // Runs the same checks as `verify_solution` and, if they pass, returns the total distance
+    // computed while checking the routes
+    pub fn evaluate(&self, challenge: &Challenge) -> Result<i64> {
+        let total_distance = checked_routes_total_distance(
+            challenge.difficulty.num_nodes,
+            challenge.max_capacity,
+            &challenge.demands,
+            &challenge.distance_matrix,
+            &challenge.depots,
+            &self.routes,
+        )?;
+        if let Some(max_routes) = challenge.max_routes {
+            if self.routes.len() > max_routes {
+                return Err(VerificationError::TooManyRoutes {
+                    actual: self.routes.len(),
+                    allowed: max_routes,
+                }
+                .into());
+            }
+        }
+        if total_distance <= challenge.max_total_distance as i64 {
+            Ok(total_distance)
+        } else {
+            Err(VerificationError::TotalDistanceExceeded {
+                actual: total_distance,
+                allowed: challenge.max_total_distance,
+            }
+            .into())
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Challenge {
     pub seed: u32,
     pub difficulty: Difficulty,
@@ -45,6 +135,16 @@ pub struct Challenge {
     pub distance_matrix: Vec<Vec<i32>>,
     pub max_total_distance: i32,
     pub max_capacity: i32,
+    #[serde(default)]
+    pub max_routes: Option<usize>,
+    // Nodes vehicles may start from. Every route must return to the depot it started from.
+    // Depots have zero demand and are not customers, so they never need to be visited
+    #[serde(default = "default_depots")]
+    pub depots: Vec<usize>,
+}
+
+fn default_depots() -> Vec<usize> {
+    vec![0]
 }
 
 impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
@@ -59,7 +159,36 @@ impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
             .collect();
         node_positions[0] = (250.0, 250.0); // Depot is node 0, and in the center
 
-        let mut demands: Vec<i32> = (0..num_nodes).map(|_| rng.gen_range(15..30)).collect();
+        let mut demands: Vec<i32> = match difficulty.demand_distribution {
+            DemandDistribution::Uniform => (0..num_nodes).map(|_| rng.gen_range(15..30)).collect(),
+            DemandDistribution::Clustered => {
+                let hotspots: Vec<(f64, f64)> = (0..3)
+                    .map(|_| (rng.gen::<f64>() * 500.0, rng.gen::<f64>() * 500.0))
+                    .collect();
+                node_positions
+                    .iter()
+                    .map(|&(x, y)| {
+                        let near_hotspot = hotspots
+                            .iter()
+                            .any(|&(hx, hy)| (x - hx).hypot(y - hy) <= 100.0);
+                        if near_hotspot {
+                            rng.gen_range(40..60)
+                        } else {
+                            rng.gen_range(5..15)
+                        }
+                    })
+                    .collect()
+            }
+            DemandDistribution::Bimodal => (0..num_nodes)
+                .map(|_| {
+                    if rng.gen_bool(0.5) {
+                        rng.gen_range(40..60)
+                    } else {
+                        rng.gen_range(5..15)
+                    }
+                })
+                .collect(),
+        };
         demands[0] = 0; // Depot demand is 0
 
         let distance_matrix: Vec<Vec<i32>> = node_positions
@@ -96,29 +225,149 @@ impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
             distance_matrix,
             max_total_distance,
             max_capacity,
+            max_routes: None,
+            depots: default_depots(),
         })
     }
 
     fn verify_solution(&self, solution: &Solution) -> Result<()> {
-        let total_distance = calc_routes_total_distance(
-            self.difficulty.num_nodes,
-            self.max_capacity,
-            &self.demands,
-            &self.distance_matrix,
-            &solution.routes,
-        )?;
-        if total_distance <= self.max_total_distance {
-            Ok(())
-        } else {
-            Err(anyhow!(
+        solution.evaluate(self).map(|_| ())
+    }
+}
+
+// Reason a set of routes is rejected. Returned wrapped in `anyhow::Error`, so callers can
+// recover it with `downcast_ref::<VerificationError>()`
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerificationError {
+    RouteNotClosed {
+        route: usize,
+    },
+    EmptyRoute {
+        route: usize,
+    },
+    DuplicateCustomer(usize),
+    CapacityExceeded {
+        route: usize,
+        load: i32,
+        capacity: i32,
+    },
+    MissingCustomer(usize),
+    TooManyRoutes {
+        actual: usize,
+        allowed: usize,
+    },
+    TotalDistanceExceeded {
+        actual: i64,
+        allowed: i32,
+    },
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationError::RouteNotClosed { route } => write!(
+                f,
+                "Route {} must start at one of the depots and return to it",
+                route
+            ),
+            VerificationError::EmptyRoute { route } => {
+                write!(f, "Route {} must visit at least one non-depot node", route)
+            }
+            VerificationError::DuplicateCustomer(node) => write!(
+                f,
+                "The same non-depot node ({}) cannot be visited more than once",
+                node
+            ),
+            VerificationError::CapacityExceeded {
+                route,
+                load,
+                capacity,
+            } => write!(
+                f,
+                "Total demand on route {} ({}) exceeds max capacity ({})",
+                route, load, capacity
+            ),
+            VerificationError::MissingCustomer(node) => {
+                write!(f, "All nodes must be visited, node {} is not", node)
+            }
+            VerificationError::TooManyRoutes { actual, allowed } => write!(
+                f,
+                "Number of routes ({}) exceeds max routes ({})",
+                actual, allowed
+            ),
+            VerificationError::TotalDistanceExceeded { actual, allowed } => write!(
+                f,
                 "Total distance ({}) exceeds max total distance ({})",
-                total_distance,
-                self.max_total_distance
-            ))
+                actual, allowed
+            ),
         }
     }
 }
 
+impl std::error::Error for VerificationError {}
+
+// Rough difficulty of an instance in [0, 1], for prioritising seeds before solving. Weighs how
+// far `max_total_distance` is below the nearest-neighbour baseline (half), how tightly the total
+// demand packs into the minimum number of vehicles (a quarter) and the spread of the distances,
+// as their coefficient of variation (a quarter). Instances with a customer that cannot fit in a
+// vehicle are unsolvable and score 1
+pub fn estimate_hardness(challenge: &Challenge) -> f64 {
+    let num_nodes = challenge.difficulty.num_nodes;
+    let capacity = challenge.max_capacity;
+    if num_nodes < 2 || capacity <= 0 {
+        return 0.0;
+    }
+    if challenge.demands.iter().any(|&demand| demand > capacity) {
+        return 1.0;
+    }
+
+    let tightness = calc_baseline_routes(
+        num_nodes,
+        capacity,
+        &challenge.demands,
+        &challenge.distance_matrix,
+    )
+    .and_then(|routes| {
+        calc_routes_total_distance(
+            num_nodes,
+            capacity,
+            &challenge.demands,
+            &challenge.distance_matrix,
+            &routes,
+        )
+    })
+    .map_or(0.0, |baseline| {
+        (1.0 - challenge.max_total_distance as f64 / baseline.max(1) as f64).clamp(0.0, 1.0)
+    });
+
+    let total_demand: i32 = challenge.demands.iter().sum();
+    let vehicles = total_demand as f64 / capacity as f64;
+    let fill = if vehicles > 0.0 {
+        vehicles / vehicles.ceil()
+    } else {
+        0.0
+    };
+
+    let distances: Vec<f64> = (0..num_nodes)
+        .flat_map(|i| (0..num_nodes).filter(move |&j| j != i).map(move |j| (i, j)))
+        .map(|(i, j)| challenge.distance_matrix[i][j] as f64)
+        .collect();
+    let mean = distances.iter().sum::<f64>() / distances.len() as f64;
+    let spread = if mean > 0.0 {
+        let variance = distances
+            .iter()
+            .map(|&distance| (distance - mean).powi(2))
+            .sum::<f64>()
+            / distances.len() as f64;
+        let cv = variance.sqrt() / mean;
+        cv / (1.0 + cv)
+    } else {
+        0.0
+    };
+
+    0.5 * tightness + 0.25 * fill + 0.25 * spread
+}
+
 pub fn calc_baseline_routes(
     num_nodes: usize,
     max_capacity: i32,
@@ -167,41 +416,416 @@ pub fn calc_routes_total_distance(
     distance_matrix: &Vec<Vec<i32>>,
     routes: &Vec<Vec<usize>>,
 ) -> Result<i32> {
+    calc_multi_depot_routes_total_distance(
+        num_nodes,
+        max_capacity,
+        demands,
+        distance_matrix,
+        &[0],
+        routes,
+    )
+}
+
+// Like `calc_routes_total_distance`, but each route may start from any of `depots` as long as
+// it returns to the same one
+pub fn calc_multi_depot_routes_total_distance(
+    num_nodes: usize,
+    max_capacity: i32,
+    demands: &[i32],
+    distance_matrix: &[Vec<i32>],
+    depots: &[usize],
+    routes: &[Vec<usize>],
+) -> Result<i32> {
+    let total_distance = checked_routes_total_distance(
+        num_nodes,
+        max_capacity,
+        demands,
+        distance_matrix,
+        depots,
+        routes,
+    )?;
+    i32::try_from(total_distance)
+        .map_err(|_| anyhow!("Total distance ({}) overflows i32", total_distance))
+}
+
+// Validates the routes and sums their distances in i64
+fn checked_routes_total_distance(
+    num_nodes: usize,
+    max_capacity: i32,
+    demands: &[i32],
+    distance_matrix: &[Vec<i32>],
+    depots: &[usize],
+    routes: &[Vec<usize>],
+) -> Result<i64> {
     let mut total_distance = 0;
     let mut visited = vec![false; num_nodes];
-    visited[0] = true;
+    for &depot in depots {
+        visited[depot] = true;
+    }
 
-    for route in routes {
-        if route.len() <= 2 || route[0] != 0 || route[route.len() - 1] != 0 {
-            return Err(anyhow!("Each route must start and end at node 0 (the depot), and visit at least one non-depot node"));
+    for (route_idx, route) in routes.iter().enumerate() {
+        if route.len() < 2 || !depots.contains(&route[0]) || route[route.len() - 1] != route[0] {
+            return Err(VerificationError::RouteNotClosed { route: route_idx }.into());
+        }
+        if route.len() == 2 {
+            return Err(VerificationError::EmptyRoute { route: route_idx }.into());
         }
 
-        let mut capacity = max_capacity;
-        let mut current_node = 0;
+        let depot = route[0];
+        let mut load = 0;
+        let mut current_node = depot;
 
         for &node in &route[1..route.len() - 1] {
             if visited[node] {
-                return Err(anyhow!(
-                    "The same non-depot node cannot be visited more than once"
-                ));
-            }
-            if demands[node] > capacity {
-                return Err(anyhow!(
-                    "The total demand on each route must not exceed max capacity"
-                ));
+                return Err(VerificationError::DuplicateCustomer(node).into());
             }
             visited[node] = true;
-            capacity -= demands[node];
-            total_distance += distance_matrix[current_node][node];
+            load += demands[node];
+            total_distance += distance_matrix[current_node][node] as i64;
             current_node = node;
         }
+        if load > max_capacity {
+            return Err(VerificationError::CapacityExceeded {
+                route: route_idx,
+                load,
+                capacity: max_capacity,
+            }
+            .into());
+        }
 
-        total_distance += distance_matrix[current_node][0];
+        total_distance += distance_matrix[current_node][depot] as i64;
     }
 
-    if visited.iter().any(|&v| !v) {
-        return Err(anyhow!("All nodes must be visited"));
+    if let Some(node) = visited.iter().position(|&v| !v) {
+        return Err(VerificationError::MissingCustomer(node).into());
     }
 
     Ok(total_distance)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChallengeTrait;
+
+    fn baseline_challenge_and_solution() -> (Challenge, Solution) {
+        let difficulty = Difficulty {
+            num_nodes: 20,
+            better_than_baseline: 0,
+            demand_distribution: DemandDistribution::Uniform,
+        };
+        let challenge = Challenge::generate_instance(0, &difficulty).unwrap();
+        let routes = calc_baseline_routes(
+            difficulty.num_nodes,
+            challenge.max_capacity,
+            &challenge.demands,
+            &challenge.distance_matrix,
+        )
+        .unwrap();
+        (challenge, Solution { routes })
+    }
+
+    #[test]
+    fn test_verify_solution_accepts_routes_within_max_routes() {
+        let (mut challenge, solution) = baseline_challenge_and_solution();
+        challenge.max_routes = Some(solution.routes.len());
+        assert!(challenge.verify_solution(&solution).is_ok());
+    }
+
+    #[test]
+    fn test_verify_solution_rejects_routes_over_max_routes() {
+        let (mut challenge, solution) = baseline_challenge_and_solution();
+        challenge.max_routes = Some(solution.routes.len() - 1);
+        assert!(challenge.verify_solution(&solution).is_err());
+    }
+
+    #[test]
+    fn test_challenge_json_round_trip() {
+        let (challenge, solution) = baseline_challenge_and_solution();
+        let reloaded: Challenge =
+            serde_json::from_str(&serde_json::to_string(&challenge).unwrap()).unwrap();
+        assert_eq!(reloaded, challenge);
+
+        let reloaded_solution: Solution =
+            serde_json::from_str(&serde_json::to_string(&solution).unwrap()).unwrap();
+        assert_eq!(reloaded_solution, solution);
+        assert!(reloaded.verify_solution(&reloaded_solution).is_ok());
+    }
+
+    fn verification_error(challenge: &Challenge, routes: Vec<Vec<usize>>) -> VerificationError {
+        challenge
+            .verify_solution(&Solution { routes })
+            .unwrap_err()
+            .downcast::<VerificationError>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_verify_solution_error_variants() {
+        let (mut challenge, solution) = baseline_challenge_and_solution();
+        let routes = solution.routes;
+
+        let mut not_closed = routes.clone();
+        not_closed[0].pop();
+        assert_eq!(
+            verification_error(&challenge, not_closed),
+            VerificationError::RouteNotClosed { route: 0 }
+        );
+
+        let mut empty = routes.clone();
+        empty.push(vec![0, 0]);
+        assert_eq!(
+            verification_error(&challenge, empty),
+            VerificationError::EmptyRoute {
+                route: routes.len()
+            }
+        );
+
+        let mut duplicate = routes.clone();
+        let node = routes[0][1];
+        duplicate[1].insert(1, node);
+        assert_eq!(
+            verification_error(&challenge, duplicate),
+            VerificationError::DuplicateCustomer(node)
+        );
+
+        // Serving the first two routes with one vehicle overloads it
+        let mut overloaded = routes.clone();
+        let second = overloaded.remove(1);
+        overloaded[0].pop();
+        overloaded[0].extend(&second[1..]);
+        let load = overloaded[0]
+            .iter()
+            .map(|&node| challenge.demands[node])
+            .sum();
+        assert_eq!(
+            verification_error(&challenge, overloaded),
+            VerificationError::CapacityExceeded {
+                route: 0,
+                load,
+                capacity: challenge.max_capacity
+            }
+        );
+
+        let mut missing = routes.clone();
+        let node = missing[0].remove(1);
+        if missing[0].len() == 2 {
+            missing.remove(0);
+        }
+        assert_eq!(
+            verification_error(&challenge, missing),
+            VerificationError::MissingCustomer(node)
+        );
+
+        challenge.max_total_distance -= 1;
+        assert_eq!(
+            verification_error(&challenge, routes),
+            VerificationError::TotalDistanceExceeded {
+                actual: challenge.max_total_distance as i64 + 1,
+                allowed: challenge.max_total_distance
+            }
+        );
+    }
+
+    fn demands_for(seed: u32, demand_distribution: DemandDistribution) -> Vec<i32> {
+        let difficulty = Difficulty {
+            num_nodes: 100,
+            better_than_baseline: 0,
+            demand_distribution,
+        };
+        Challenge::generate_instance(seed, &difficulty)
+            .unwrap()
+            .demands
+    }
+
+    fn variance(demands: &[i32]) -> f64 {
+        let customers = &demands[1..];
+        let mean = customers.iter().sum::<i32>() as f64 / customers.len() as f64;
+        customers
+            .iter()
+            .map(|&demand| (demand as f64 - mean).powi(2))
+            .sum::<f64>()
+            / customers.len() as f64
+    }
+
+    #[test]
+    fn test_generate_instance_demands_are_deterministic() {
+        for demand_distribution in [
+            DemandDistribution::Uniform,
+            DemandDistribution::Clustered,
+            DemandDistribution::Bimodal,
+        ] {
+            let demands = demands_for(7, demand_distribution);
+            assert_eq!(demands, demands_for(7, demand_distribution));
+            assert_eq!(demands[0], 0);
+            assert!(demands[1..]
+                .iter()
+                .all(|&demand| demand > 0 && demand <= 100));
+        }
+    }
+
+    #[test]
+    fn test_clustered_demands_vary_more_than_uniform() {
+        for seed in 0..5 {
+            assert!(
+                variance(&demands_for(seed, DemandDistribution::Clustered))
+                    > variance(&demands_for(seed, DemandDistribution::Uniform))
+            );
+        }
+    }
+
+    #[test]
+    fn test_solution_metrics() {
+        let challenge = Challenge {
+            seed: 0,
+            difficulty: Difficulty {
+                num_nodes: 5,
+                better_than_baseline: 0,
+                demand_distribution: DemandDistribution::Uniform,
+            },
+            demands: vec![0, 10, 20, 30, 40],
+            distance_matrix: vec![
+                vec![0, 1, 2, 3, 4],
+                vec![1, 0, 5, 6, 7],
+                vec![2, 5, 0, 8, 9],
+                vec![3, 6, 8, 0, 10],
+                vec![4, 7, 9, 10, 0],
+            ],
+            max_total_distance: 100,
+            max_capacity: 100,
+            max_routes: None,
+            depots: vec![0],
+        };
+        let solution = Solution {
+            routes: vec![vec![0, 1, 2, 0], vec![0, 3, 4, 0]],
+        };
+        assert_eq!(
+            solution.metrics(&challenge),
+            SolutionMetrics {
+                num_routes: 2,
+                // (1 + 5 + 2) + (3 + 10 + 4)
+                total_distance: 25,
+                max_route_load: 70,
+                min_route_load: 30,
+                total_demand_served: 100,
+            }
+        );
+        assert_eq!(
+            solution.metrics(&challenge).total_distance,
+            calc_routes_total_distance(
+                5,
+                challenge.max_capacity,
+                &challenge.demands,
+                &challenge.distance_matrix,
+                &solution.routes
+            )
+            .unwrap() as i64
+        );
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let (challenge, solution) = baseline_challenge_and_solution();
+        let d = &challenge.distance_matrix;
+        let mut expected = 0;
+        for route in &solution.routes {
+            for k in 1..route.len() {
+                expected += d[route[k - 1]][route[k]] as i64;
+            }
+        }
+        assert_eq!(solution.evaluate(&challenge).unwrap(), expected);
+
+        // Dropping a route leaves its customers unvisited
+        let invalid = Solution {
+            routes: solution.routes[1..].to_vec(),
+        };
+        assert!(invalid.evaluate(&challenge).is_err());
+        assert!(challenge.verify_solution(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_calc_routes_total_distance_overflow() {
+        let distance_matrix = vec![vec![i32::MAX; 3]; 3];
+        let routes = vec![vec![0, 1, 2, 0]];
+        assert!(
+            calc_routes_total_distance(3, 100, &vec![0, 1, 1], &distance_matrix, &routes).is_err()
+        );
+    }
+
+    #[test]
+    fn test_estimate_hardness_increases_with_better_than_baseline() {
+        for seed in 0..3 {
+            let hardness: Vec<f64> = [0, 100, 200, 300, 400]
+                .into_iter()
+                .map(|better_than_baseline| {
+                    let difficulty = Difficulty {
+                        num_nodes: 30,
+                        better_than_baseline,
+                        demand_distribution: DemandDistribution::Uniform,
+                    };
+                    let challenge = Challenge::generate_instance(seed, &difficulty).unwrap();
+                    let hardness = estimate_hardness(&challenge);
+                    assert_eq!(hardness, estimate_hardness(&challenge));
+                    assert!((0.0..=1.0).contains(&hardness));
+                    hardness
+                })
+                .collect();
+            assert!(hardness.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test]
+    fn test_verify_solution_multi_depot() {
+        let (mut challenge, solution) = baseline_challenge_and_solution();
+        let depot = solution.routes[0][1];
+        challenge.demands[depot] = 0;
+        challenge.depots = vec![0, depot];
+
+        // The second depot no longer has to be visited as a customer
+        let mut routes: Vec<Vec<usize>> = solution
+            .routes
+            .iter()
+            .map(|route| {
+                route
+                    .iter()
+                    .cloned()
+                    .filter(|&node| node != depot)
+                    .collect()
+            })
+            .filter(|route: &Vec<usize>| route.len() > 2)
+            .collect();
+        challenge.max_total_distance = i32::MAX;
+        assert!(challenge
+            .verify_solution(&Solution {
+                routes: routes.clone()
+            })
+            .is_ok());
+
+        // A route may start from the second depot but must return to it
+        let customer = routes[0].remove(1);
+        if routes[0].len() == 2 {
+            routes.remove(0);
+        }
+        routes.push(vec![depot, customer, depot]);
+        assert!(challenge
+            .verify_solution(&Solution {
+                routes: routes.clone()
+            })
+            .is_ok());
+        routes.last_mut().unwrap()[2] = 0;
+        assert_eq!(
+            verification_error(&challenge, routes.clone()),
+            VerificationError::RouteNotClosed {
+                route: routes.len() - 1
+            }
+        );
+        challenge.depots = vec![0];
+        routes.last_mut().unwrap()[2] = depot;
+        assert_eq!(
+            verification_error(&challenge, routes.clone()),
+            VerificationError::RouteNotClosed {
+                route: routes.len() - 1
+            }
+        );
+    }
+}